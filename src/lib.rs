@@ -39,12 +39,61 @@ static RAIN_MAX: usize = 500;
 static DMG_FRAMES: u8 = 16;
 static NO_INPUT_FRAMES: u8 = 120;
 static NO_INPUT_FRAMES_SH: u8 = 48;
-static POWERUP_FRAMES: u16 = 600;
 static MAX_LVL: usize = 8;
 static MAX_DIFF: u8 = 8;
-static MAX_HP: u8 = 8;
+static MAX_HP: u8 = 16; // Half hearts
+static INV_FRAMES: u8 = 60; // Can't be hurt again right after a hit
 static DOOR_TIMER: u16 = 128;
 static DIRT_START: u8 = 24;
+static BOMB_FUSE: u16 = 96;
+static BOMB_RADIUS: usize = 12;
+static BOMB_MAX: u8 = 9;
+static HIT_FRAMES: u8 = 12;
+static FX_FRAMES: u8 = 16;
+static UFO_CYCLE: u16 = 300;
+static UFO_BEAM_FRAMES: u16 = 120;
+static PATH_CELL: usize = 4;
+static PATH_GRID: usize = WORLD_SIZE / PATH_CELL;
+static PATH_INTERVAL: u32 = 24; // Frames between path updates
+static PATH_UNSEEN: u8 = 254;
+static PATH_BLOCKED: u8 = 255;
+static SEEKER_SIGHT: f32 = 96.;
+static SEEKER_MEMORY: u16 = 180; // Frames a seeker keeps chasing after losing sight
+static PROJ_MAX: usize = 32;
+static PROJ_SUB: i16 = 8; // Projectile positions are in 1/8 pixels
+static TURRET_RATE: u16 = 90;
+static TURRET_RANGE: f32 = 96.;
+static WORM_LEN: usize = 5; // Body segments
+static WORM_GAP: usize = 4; // Head steps between segments
+static WORM_HEARING: f32 = 96.;
+static BOSS_HP: u16 = 48;
+static BOSS_SIZE: i16 = 16;
+static BOSS_BLAST_MAX: u16 = 6; // Most a single blast can take off the boss
+static CRUSH_MASS: u16 = 12; // Blocks landing on the player before it hurts
+static CRUSH_ENEMY: u8 = 4; // Blocks landing on an enemy in one go before it hurts
+static CAVEIN_STRESS: u8 = 6; // Drilling checks under an overhang before it gives
+static CAVEIN_WARN: u8 = 90; // Frames of rumbling before a cave-in
+static VENT_COOLDOWN: u16 = 180;
+static SHOP_STOCK: usize = 4; // Catalogue items on sale each day
+static SHOP_REROLL: u16 = 3; // Goes up by 2 for each reroll in a day
+static HELD_MAX: u8 = 2; // Powerup slots after the pocket upgrade
+static COST_POCKET: u16 = 20;
+static DIRECTOR_INTERVAL: u32 = 600; // Frames between difficulty reviews
+static DIRECTOR_MIN: i8 = -3;
+static DIRECTOR_MAX: i8 = 3;
+static COOLANT_MAX: u8 = 3; // Extra canisters a day from the shop
+static PALS_FREE: usize = 3; // Palettes open before any unlocks
+static SAVE_SIZE: usize = 10; // Bytes of disk used by Save
+static TOAST_FRAMES: u16 = 180;
+static LIVES_START: u8 = 3;
+static LIVES_MAX: u8 = 5;
+static LIFE_GOLD_LOSS: u16 = 25; // Percent of gold dropped on each death
+static RESPAWN_FRAMES: u16 = 90;
+static CONTINUE_FRAMES: u16 = 600;
+static CONTINUE_COST: u16 = 15; // Goes up with each continue
+static CANARY_HP: u8 = 3;
+static CANARY_LAG: usize = 12; // Player steps the canary trails behind
+static SAVE_MAGIC: [u8; 3] = *b"AR1";
 static MUSIC_ENABLED: bool = true;
 
 // Color palettes
@@ -125,6 +174,127 @@ const SMILEY3: [u8; 8] = [
     0b10011111,
 ];
 #[rustfmt::skip]
+const HOOD1: [u8; 8] = [
+    0b10000001,
+    0b00000000,
+    0b01011010,
+    0b01111110,
+    0b00000000,
+    0b00000000,
+    0b10000001,
+    0b10011001,
+];
+#[rustfmt::skip]
+const HOOD2: [u8; 8] = [
+    0b10000001,
+    0b00000000,
+    0b01011010,
+    0b01111110,
+    0b00000000,
+    0b00000000,
+    0b10000001,
+    0b11111001,
+];
+#[rustfmt::skip]
+const HOOD3: [u8; 8] = [
+    0b10000001,
+    0b00000000,
+    0b01011010,
+    0b01111110,
+    0b00000000,
+    0b00000000,
+    0b10000001,
+    0b10011111,
+];
+#[rustfmt::skip]
+const SPIKY1: [u8; 8] = [
+    0b01010101,
+    0b10000001,
+    0b10110111,
+    0b10000001,
+    0b00000000,
+    0b00000000,
+    0b10000001,
+    0b10011001,
+];
+#[rustfmt::skip]
+const SPIKY2: [u8; 8] = [
+    0b01010101,
+    0b10000001,
+    0b10110111,
+    0b10000001,
+    0b00000000,
+    0b00000000,
+    0b10000001,
+    0b11111001,
+];
+#[rustfmt::skip]
+const SPIKY3: [u8; 8] = [
+    0b01010101,
+    0b10000001,
+    0b10110111,
+    0b10000001,
+    0b00000000,
+    0b00000000,
+    0b10000001,
+    0b10011111,
+];
+#[rustfmt::skip]
+const DOT1: [u8; 8] = [
+    0b11111111,
+    0b11000011,
+    0b10100101,
+    0b11000011,
+    0b10000001,
+    0b10000001,
+    0b11011011,
+    0b11011011,
+];
+#[rustfmt::skip]
+const DOT2: [u8; 8] = [
+    0b11111111,
+    0b11000011,
+    0b10100101,
+    0b11000011,
+    0b10000001,
+    0b10000001,
+    0b11011011,
+    0b11111011,
+];
+#[rustfmt::skip]
+const DOT3: [u8; 8] = [
+    0b11111111,
+    0b11000011,
+    0b10100101,
+    0b11000011,
+    0b10000001,
+    0b10000001,
+    0b11011011,
+    0b11011111,
+];
+#[rustfmt::skip]
+const CANARY1: [u8; 8] = [
+    0b11111111,
+    0b11110011,
+    0b11100001,
+    0b00000011,
+    0b10000111,
+    0b11000111,
+    0b11101111,
+    0b11111111,
+];
+#[rustfmt::skip]
+const CANARY2: [u8; 8] = [
+    0b11111111,
+    0b11110011,
+    0b11100001,
+    0b10000011,
+    0b00000111,
+    0b11000111,
+    0b11101111,
+    0b11111111,
+];
+#[rustfmt::skip]
 const SMILEYDEAD: [u8; 8] = [
     0b11111111,
     0b11110011,
@@ -159,6 +329,28 @@ const DRILL2: [u8; 8] = [
     0b11111111,
 ];
 #[rustfmt::skip]
+const DRILLW1: [u8; 8] = [
+    0b11111111,
+    0b10111111,
+    0b10001111,
+    0b00000111,
+    0b00000011,
+    0b10000111,
+    0b10011111,
+    0b11111111,
+];
+#[rustfmt::skip]
+const DRILLW2: [u8; 8] = [
+    0b11111111,
+    0b10011111,
+    0b00000111,
+    0b00000011,
+    0b10000111,
+    0b10001111,
+    0b10111111,
+    0b11111111,
+];
+#[rustfmt::skip]
 const DRILLD1: [u8; 8] = [
     0b01011111,
     0b10011111,
@@ -215,6 +407,17 @@ const HEART: [u8; 8] = [
     0b11100111,
 ];
 #[rustfmt::skip]
+const HEART_HALF: [u8; 8] = [
+    0b10011111,
+    0b00011111,
+    0b00001111,
+    0b00001111,
+    0b00001111,
+    0b10001111,
+    0b11001111,
+    0b11101111,
+];
+#[rustfmt::skip]
 const GOLD1: [u8; 8] = [
     0b10011111,
     0b00101111,
@@ -369,6 +572,116 @@ const BOMBER2: [u8; 8] = [
     0b10000001,
 ];
 #[rustfmt::skip]
+const UFO1: [u8; 8] = [
+    0b11100111,
+    0b11000011,
+    0b00000000,
+    0b01011010,
+    0b00000000,
+    0b11000011,
+    0b11111111,
+    0b11111111,
+];
+#[rustfmt::skip]
+const UFO2: [u8; 8] = [
+    0b11100111,
+    0b11000011,
+    0b00000000,
+    0b10100101,
+    0b00000000,
+    0b11000011,
+    0b11111111,
+    0b11111111,
+];
+#[rustfmt::skip]
+const TURRET1: [u8; 8] = [
+    0b11100111,
+    0b11100111,
+    0b11000011,
+    0b10000001,
+    0b00011000,
+    0b00011000,
+    0b10000001,
+    0b11000011,
+];
+#[rustfmt::skip]
+const TURRET2: [u8; 8] = [
+    0b11100111,
+    0b11100111,
+    0b11000011,
+    0b10000001,
+    0b00100100,
+    0b00100100,
+    0b10000001,
+    0b11000011,
+];
+#[rustfmt::skip]
+const WORM_HEAD1: [u8; 8] = [
+    0b10000111,
+    0b00000011,
+    0b01001011,
+    0b00000011,
+    0b00110011,
+    0b10000111,
+    0b11111111,
+    0b11111111,
+];
+#[rustfmt::skip]
+const WORM_HEAD2: [u8; 8] = [
+    0b10000111,
+    0b00000011,
+    0b01001011,
+    0b00000011,
+    0b00000011,
+    0b10110111,
+    0b11111111,
+    0b11111111,
+];
+#[rustfmt::skip]
+const WORM_BODY: [u8; 8] = [
+    0b11001111,
+    0b10000111,
+    0b01111011,
+    0b01111011,
+    0b10000111,
+    0b11001111,
+    0b11111111,
+    0b11111111,
+];
+#[rustfmt::skip]
+const BOMB1: [u8; 8] = [
+    0b11111001,
+    0b11110111,
+    0b11000011,
+    0b10000001,
+    0b10011001,
+    0b10000001,
+    0b11000011,
+    0b11111111,
+];
+#[rustfmt::skip]
+const BOMB2: [u8; 8] = [
+    0b11111010,
+    0b11110111,
+    0b11000011,
+    0b10000001,
+    0b10000001,
+    0b10000001,
+    0b11000011,
+    0b11111111,
+];
+#[rustfmt::skip]
+const COOLANT: [u8; 8] = [
+    0b11100111,
+    0b11000011,
+    0b10011001,
+    0b10111101,
+    0b10011001,
+    0b10111101,
+    0b10000001,
+    0b11000011,
+];
+#[rustfmt::skip]
 const PU1: [u8; 8] = [
     0b10000001,
     0b01111110,
@@ -403,6 +716,45 @@ const EXC: [u8; 8] = [
     0b11100111,
 ];
 
+#[rustfmt::skip]
+const BOSS1: [u8; 32] = [
+    0b11111111, 0b11111111,
+    0b11000000, 0b00000011,
+    0b10011111, 0b11111001,
+    0b10010011, 0b11001001,
+    0b10010011, 0b11001001,
+    0b10011111, 0b11111001,
+    0b00000000, 0b00000000,
+    0b01101101, 0b10110110,
+    0b00000000, 0b00000000,
+    0b11000000, 0b00000011,
+    0b11100100, 0b10010111,
+    0b11110000, 0b00001111,
+    0b11111001, 0b00011111,
+    0b11111100, 0b00111111,
+    0b11111110, 0b01111111,
+    0b11111111, 0b11111111,
+];
+#[rustfmt::skip]
+const BOSS2: [u8; 32] = [
+    0b11111111, 0b11111111,
+    0b11000000, 0b00000011,
+    0b10011111, 0b11111001,
+    0b10011001, 0b10011001,
+    0b10011001, 0b10011001,
+    0b10011111, 0b11111001,
+    0b00000000, 0b00000000,
+    0b01011011, 0b01101101,
+    0b00000000, 0b00000000,
+    0b11000000, 0b00000011,
+    0b11100000, 0b00000111,
+    0b11110010, 0b01001111,
+    0b11111000, 0b00011111,
+    0b11111100, 0b10111111,
+    0b11111110, 0b01111111,
+    0b11111111, 0b11111111,
+];
+
 #[rustfmt::skip]
 const LOGO_A: [u8; 32] = [
     0b11110000, 0b00001111,
@@ -612,71 +964,692 @@ enum Screen {
     Game,
     GameOver,
     Shop,
+    Workshop,
     Transition,
+    Draft,
+    Mutators,
+    Unlocks,
+    Gallery,
+    Miners,
+    Continue,
 }
 
-#[derive(Copy, Clone)]
-struct LVlSettings {
-    drone_limit: usize,
-    fly_limit: usize,
-    slider_limit: usize,
-    seeker_limit: usize,
-    bomber_limit: usize,
-    drone_rte: u16,
-    rain_chance_rte: u16, // Higher is less chance
-    rain_amount_rte: u16, // Higher is less amount
-    rain_acidity: u8,
-    gold_amt: usize,
-    text: &'static [u8],
+// Earned once and kept on disk, bit per ACHIEVEMENT_TYPES
+#[derive(Copy, Clone, PartialEq)]
+enum Achievement {
+    HandsOff,
+    Hell,
+    BomberPop,
+    IceCold,
+    GoldRush,
+    Slayer,
+    Untouched,
 }
-impl LVlSettings {
-    fn new() -> Self {
-        Self {
-            drone_limit: 0,
-            fly_limit: 0,
-            slider_limit: 0,
-            seeker_limit: 0,
-            bomber_limit: 0,
-            drone_rte: 100,
-            rain_chance_rte: 100,
-            rain_amount_rte: 200,
-            rain_acidity: 50,
-            gold_amt: 10,
-            text: b"",
+
+const ACHIEVEMENT_TYPES: [Achievement; 7] = [
+    Achievement::HandsOff,
+    Achievement::Hell,
+    Achievement::BomberPop,
+    Achievement::IceCold,
+    Achievement::GoldRush,
+    Achievement::Slayer,
+    Achievement::Untouched,
+];
+
+impl Achievement {
+    fn name(&self) -> &'static str {
+        match self {
+            Achievement::HandsOff => "HANDS OFF",
+            Achievement::Hell => "HELLRAISER",
+            Achievement::BomberPop => "POP POP",
+            Achievement::IceCold => "ICE COLD",
+            Achievement::GoldRush => "GOLD RUSH",
+            Achievement::Slayer => "SLAYER",
+            Achievement::Untouched => "UNTOUCHED",
         }
     }
-    fn apply_difficulty(&mut self, difficulty: u8) {
-        self.drone_limit *= difficulty as usize;
-        self.fly_limit *= difficulty as usize;
-        self.slider_limit *= difficulty as usize;
-        self.seeker_limit *= difficulty as usize;
-        self.bomber_limit *= difficulty as usize;
-        if difficulty >= 1 {
-            self.drone_rte = self.drone_rte.saturating_div(difficulty as u16);
-            self.rain_chance_rte = self.rain_chance_rte.saturating_div(difficulty as u16);
-            self.rain_amount_rte = self.rain_amount_rte.saturating_div(difficulty as u16);
-        } else {
-            // Avoid div by 0
-            self.drone_rte = 999;
-            self.rain_chance_rte = 999;
-            self.rain_amount_rte = 999;
+
+    fn desc(&self) -> &'static str {
+        match self {
+            Achievement::HandsOff => "NO DIG ABOVE DIRT",
+            Achievement::Hell => "FINISH ON HELL",
+            Achievement::BomberPop => "5 BOMBERS 1 DAY",
+            Achievement::IceCold => "WIN, NO OVERHEATS",
+            Achievement::GoldRush => "ALL GOLD IN A DAY",
+            Achievement::Slayer => "BEAT THE BOSS",
+            Achievement::Untouched => "DAY WITH NO DMG",
         }
     }
 }
 
-const LVLS: [LVlSettings; MAX_LVL] = [
-    // Zero is for special modes
-    LVlSettings {
-        drone_limit: 0,
+// Optional rules picked before a run, harder runs score more
+#[derive(Copy, Clone, PartialEq)]
+enum Mutator {
+    DoubleRain,
+    NoShop,
+    GlassMiner,
+    HeavyGold,
+    FastEnemies,
+    NoPowerups,
+}
+const MUTATOR_TYPES: [Mutator; 6] = [
+    Mutator::DoubleRain,
+    Mutator::NoShop,
+    Mutator::GlassMiner,
+    Mutator::HeavyGold,
+    Mutator::FastEnemies,
+    Mutator::NoPowerups,
+];
+impl Mutator {
+    fn name(&self) -> &'static str {
+        match self {
+            Mutator::DoubleRain => "2X RAIN",
+            Mutator::NoShop => "NO SHOP",
+            Mutator::GlassMiner => "GLASS",
+            Mutator::HeavyGold => "HVY GOLD",
+            Mutator::FastEnemies => "FAST",
+            Mutator::NoPowerups => "NO PWRUP",
+        }
+    }
+    fn desc(&self) -> &'static str {
+        match self {
+            Mutator::DoubleRain => "TWICE THE RAIN",
+            Mutator::NoShop => "SKIP THE SHOPS",
+            Mutator::GlassMiner => "ONE HEART ONLY",
+            Mutator::HeavyGold => "GOLD FALLS FAST",
+            Mutator::FastEnemies => "ENEMIES MOVE FAST",
+            Mutator::NoPowerups => "NO POWERUPS",
+        }
+    }
+    // Percent added to the score multiplier
+    fn bonus(&self) -> u16 {
+        match self {
+            Mutator::DoubleRain => 50,
+            Mutator::NoShop => 50,
+            Mutator::GlassMiner => 100,
+            Mutator::HeavyGold => 25,
+            Mutator::FastEnemies => 50,
+            Mutator::NoPowerups => 25,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum ShopItem {
+    Heart,
+    DrillSpeed,
+    DrillCool,
+    Bomb,
+    Armour,
+    Powerup,
+    Coolant,
+    Life,
+}
+
+struct ShopEntry {
+    item: ShopItem,
+    name: &'static str,
+    cost: u16,
+    step: u16, // Percent the price goes up after each purchase
+}
+
+const SHOP_CATALOGUE: [ShopEntry; 8] = [
+    ShopEntry {
+        item: ShopItem::Heart,
+        name: "HEART PIECE",
+        cost: 8,
+        step: 25,
+    },
+    ShopEntry {
+        item: ShopItem::DrillSpeed,
+        name: "DRILL SPEED",
+        cost: 16,
+        step: 25,
+    },
+    ShopEntry {
+        item: ShopItem::DrillCool,
+        name: "DRILL COOLR",
+        cost: 16,
+        step: 25,
+    },
+    ShopEntry {
+        item: ShopItem::Bomb,
+        name: "BOMB",
+        cost: 4,
+        step: 10,
+    },
+    ShopEntry {
+        item: ShopItem::Armour,
+        name: "ARMOUR",
+        cost: 12,
+        step: 50,
+    },
+    ShopEntry {
+        item: ShopItem::Powerup,
+        name: "MYSTERY BOX",
+        cost: 14,
+        step: 20,
+    },
+    ShopEntry {
+        item: ShopItem::Coolant,
+        name: "COOLANT",
+        cost: 10,
+        step: 30,
+    },
+    ShopEntry {
+        item: ShopItem::Life,
+        name: "EXTRA LIFE",
+        cost: 30,
+        step: 50,
+    },
+];
+
+// Always on trait that comes with a miner
+#[derive(Copy, Clone, PartialEq)]
+enum Passive {
+    AcidSkin,
+    HotDrill,
+    MagnetSuit,
+}
+
+impl Passive {
+    fn name(&self) -> &'static str {
+        match self {
+            Passive::AcidSkin => "ACID SKIN",
+            Passive::HotDrill => "HOT DRILL",
+            Passive::MagnetSuit => "MAGNET SUIT",
+        }
+    }
+
+    fn desc(&self) -> &'static str {
+        match self {
+            Passive::AcidSkin => "RAIN HURTS LESS",
+            Passive::HotDrill => "HITS HARD, RUNS HOT",
+            Passive::MagnetSuit => "PULLS NEARBY GOLD",
+        }
+    }
+}
+
+struct Miner {
+    name: &'static str,
+    sprites: [[u8; 8]; 3], // Idle then walking
+    hp: i8,                // Half hearts on top of the difficulty's
+    drill_speed: u8,
+    drill_heat_max: u16,
+    hitbox: i16, // Pixels across, centred in the sprite
+    passive: Option<Passive>,
+}
+
+const MINERS: [Miner; 4] = [
+    Miner {
+        name: "SMILEY",
+        sprites: [SMILEY1, SMILEY2, SMILEY3],
+        hp: 0,
+        drill_speed: 48,
+        drill_heat_max: 256,
+        hitbox: 8,
+        passive: None,
+    },
+    Miner {
+        name: "HOODIE",
+        sprites: [HOOD1, HOOD2, HOOD3],
+        hp: -2,
+        drill_speed: 40,
+        drill_heat_max: 256,
+        hitbox: 8,
+        passive: Some(Passive::AcidSkin),
+    },
+    Miner {
+        name: "SPIKE",
+        sprites: [SPIKY1, SPIKY2, SPIKY3],
+        hp: 0,
+        drill_speed: 56,
+        drill_heat_max: 320,
+        hitbox: 8,
+        passive: Some(Passive::HotDrill),
+    },
+    Miner {
+        name: "DOT",
+        sprites: [DOT1, DOT2, DOT3],
+        hp: -4,
+        drill_speed: 48,
+        drill_heat_max: 256,
+        hitbox: 6,
+        passive: Some(Passive::MagnetSuit),
+    },
+];
+
+// Kits a run can start with once unlocked
+struct Loadout {
+    name: &'static str,
+    bombs: u8,
+    drill_speed: u8,
+    hp: u8, // Half hearts on top of the difficulty's
+}
+
+const LOADOUTS: [Loadout; 4] = [
+    Loadout {
+        name: "STANDARD",
+        bombs: 0,
+        drill_speed: 0,
+        hp: 0,
+    },
+    Loadout {
+        name: "DEMO KIT",
+        bombs: 3,
+        drill_speed: 0,
+        hp: 0,
+    },
+    Loadout {
+        name: "DRILL KIT",
+        bombs: 0,
+        drill_speed: 16,
+        hp: 0,
+    },
+    Loadout {
+        name: "MEDIC KIT",
+        bombs: 0,
+        drill_speed: 0,
+        hp: 4,
+    },
+];
+
+// What lifetime gold opens up
+#[derive(Copy, Clone, PartialEq)]
+enum Unlock {
+    Loadout(usize),  // Index into LOADOUTS
+    Palettes(usize), // Palettes open up to here
+    Shop(ShopItem),
+    Mode(u8),
+}
+
+struct UnlockEntry {
+    name: &'static str,
+    gold: u32,
+    unlock: Unlock,
+}
+
+const UNLOCKS: [UnlockEntry; 7] = [
+    UnlockEntry {
+        name: "DEMO KIT",
+        gold: 50,
+        unlock: Unlock::Loadout(1),
+    },
+    UnlockEntry {
+        name: "PALETTES I",
+        gold: 120,
+        unlock: Unlock::Palettes(6),
+    },
+    UnlockEntry {
+        name: "COOLANT",
+        gold: 250,
+        unlock: Unlock::Shop(ShopItem::Coolant),
+    },
+    UnlockEntry {
+        name: "DRILL KIT",
+        gold: 400,
+        unlock: Unlock::Loadout(2),
+    },
+    UnlockEntry {
+        name: "SANDBOX",
+        gold: 600,
+        unlock: Unlock::Mode(3),
+    },
+    UnlockEntry {
+        name: "PALETTES II",
+        gold: 900,
+        unlock: Unlock::Palettes(PALS.len()),
+    },
+    UnlockEntry {
+        name: "MEDIC KIT",
+        gold: 1500,
+        unlock: Unlock::Loadout(3),
+    },
+];
+
+// Picked between days, each one can only be taken once a run
+#[derive(Copy, Clone, PartialEq)]
+enum Perk {
+    Magnetism,  // Gold drifts over from nearby
+    RainCoat,   // First rain drop each day does nothing
+    QuickDoor,  // Doors open in half the time
+    Stillness,  // Seekers can't see you while you stand still
+    Scavenger,  // Enemies drop more gold
+    Bargain,    // Shop prices are 20% off
+    CoolHead,   // Drill cools down faster
+    Demolition, // Bigger bomb blasts
+}
+const PERK_TYPES: [Perk; 8] = [
+    Perk::Magnetism,
+    Perk::RainCoat,
+    Perk::QuickDoor,
+    Perk::Stillness,
+    Perk::Scavenger,
+    Perk::Bargain,
+    Perk::CoolHead,
+    Perk::Demolition,
+];
+impl Perk {
+    fn name(&self) -> &'static str {
+        match self {
+            Perk::Magnetism => "MAGNETISM",
+            Perk::RainCoat => "RAIN COAT",
+            Perk::QuickDoor => "QUICK DOOR",
+            Perk::Stillness => "STILLNESS",
+            Perk::Scavenger => "SCAVENGER",
+            Perk::Bargain => "BARGAIN",
+            Perk::CoolHead => "COOL HEAD",
+            Perk::Demolition => "DEMOLITION",
+        }
+    }
+    fn desc(&self) -> &'static str {
+        match self {
+            Perk::Magnetism => "PULL NEARBY GOLD",
+            Perk::RainCoat => "BLOCK 1ST DROP/DAY",
+            Perk::QuickDoor => "DOORS OPEN FASTER",
+            Perk::Stillness => "HIDE WHEN STILL",
+            Perk::Scavenger => "MORE ENEMY LOOT",
+            Perk::Bargain => "SHOP 20% OFF",
+            Perk::CoolHead => "DRILL COOLS FASTER",
+            Perk::Demolition => "BIGGER BOMBS",
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum DrillPart {
+    Wide,     // Bit grows 2px on each side
+    Reach,    // Bit reaches 3px further
+    Diagonal, // Drills the corner when digging up or down while moving
+    Pierce,   // Double damage to enemies and hits 8px further through the dirt
+}
+
+struct DrillUpgrade {
+    name: &'static str,
+    part: DrillPart,
+    cost: u16,
+    needs: Option<usize>, // Index of the upgrade that unlocks this one
+}
+
+const DRILL_TREE: [DrillUpgrade; 6] = [
+    DrillUpgrade {
+        name: "WIDE BIT",
+        part: DrillPart::Wide,
+        cost: 12,
+        needs: None,
+    },
+    DrillUpgrade {
+        name: "WIDER BIT",
+        part: DrillPart::Wide,
+        cost: 24,
+        needs: Some(0),
+    },
+    DrillUpgrade {
+        name: "LONG SHAFT",
+        part: DrillPart::Reach,
+        cost: 12,
+        needs: None,
+    },
+    DrillUpgrade {
+        name: "LONGER SHAFT",
+        part: DrillPart::Reach,
+        cost: 24,
+        needs: Some(2),
+    },
+    DrillUpgrade {
+        name: "DIAGONAL",
+        part: DrillPart::Diagonal,
+        cost: 20,
+        needs: Some(0),
+    },
+    DrillUpgrade {
+        name: "PIERCER",
+        part: DrillPart::Pierce,
+        cost: 30,
+        needs: Some(2),
+    },
+];
+
+// Balance for each difficulty, scales are percentages
+struct DiffProfile {
+    name: &'static str,
+    enemy_scale: u16, // Enemy limits
+    spawn_scale: u16, // Frames between spawns, lower spawns faster
+    rain_scale: u16,  // Frames between rain, lower rains harder
+    start_hp: u8,     // Half hearts
+    price_scale: u16,
+    gold_yield: u16, // Gold per pickup
+    powerup_frames: u16,
+    crush: bool,        // Falling dirt hurts
+    overheat_stun: u8,  // Frames frozen when the drill overheats
+    overheat_dmg: bool, // Overheating burns the player
+}
+impl DiffProfile {
+    fn price(&self, cost: u16) -> u16 {
+        (cost as u32 * self.price_scale as u32 / 100).max(1) as u16
+    }
+}
+
+const DIFFS: [DiffProfile; MAX_DIFF as usize] = [
+    DiffProfile {
+        name: "BABY",
+        enemy_scale: 50,
+        spawn_scale: 300,
+        rain_scale: 400,
+        start_hp: 16,
+        price_scale: 50,
+        gold_yield: 2,
+        powerup_frames: 900,
+        crush: false,
+        overheat_stun: 0,
+        overheat_dmg: false,
+    },
+    DiffProfile {
+        name: "EASY",
+        enemy_scale: 100,
+        spawn_scale: 150,
+        rain_scale: 200,
+        start_hp: 12,
+        price_scale: 75,
+        gold_yield: 2,
+        powerup_frames: 780,
+        crush: false,
+        overheat_stun: 0,
+        overheat_dmg: false,
+    },
+    DiffProfile {
+        name: "MEDIUM",
+        enemy_scale: 150,
+        spawn_scale: 100,
+        rain_scale: 100,
+        start_hp: 10,
+        price_scale: 100,
+        gold_yield: 1,
+        powerup_frames: 660,
+        crush: true,
+        overheat_stun: 0,
+        overheat_dmg: false,
+    },
+    DiffProfile {
+        name: "HARD",
+        enemy_scale: 200,
+        spawn_scale: 70,
+        rain_scale: 75,
+        start_hp: 8,
+        price_scale: 110,
+        gold_yield: 1,
+        powerup_frames: 600,
+        crush: true,
+        overheat_stun: 30,
+        overheat_dmg: false,
+    },
+    DiffProfile {
+        name: "WILD",
+        enemy_scale: 275,
+        spawn_scale: 45,
+        rain_scale: 60,
+        start_hp: 8,
+        price_scale: 120,
+        gold_yield: 1,
+        powerup_frames: 540,
+        crush: true,
+        overheat_stun: 45,
+        overheat_dmg: false,
+    },
+    DiffProfile {
+        name: "OHNO!",
+        enemy_scale: 325,
+        spawn_scale: 40,
+        rain_scale: 40,
+        start_hp: 6,
+        price_scale: 130,
+        gold_yield: 1,
+        powerup_frames: 450,
+        crush: true,
+        overheat_stun: 60,
+        overheat_dmg: true,
+    },
+    DiffProfile {
+        name: "HECK",
+        enemy_scale: 400,
+        spawn_scale: 30,
+        rain_scale: 35,
+        start_hp: 6,
+        price_scale: 145,
+        gold_yield: 1,
+        powerup_frames: 400,
+        crush: true,
+        overheat_stun: 60,
+        overheat_dmg: true,
+    },
+    DiffProfile {
+        name: "HELL",
+        enemy_scale: 500,
+        spawn_scale: 25,
+        rain_scale: 25,
+        start_hp: 4,
+        price_scale: 160,
+        gold_yield: 1,
+        powerup_frames: 300,
+        crush: true,
+        overheat_stun: 90,
+        overheat_dmg: true,
+    },
+];
+
+#[derive(Copy, Clone)]
+struct LVlSettings {
+    drone_limit: usize,
+    fly_limit: usize,
+    slider_limit: usize,
+    seeker_limit: usize,
+    bomber_limit: usize,
+    ufo_limit: usize,
+    turret_limit: usize,
+    worm_limit: usize,
+    drone_rte: u16,
+    ufo_rte: u16,
+    rain_chance_rte: u16, // Higher is less chance
+    rain_amount_rte: u16, // Higher is less amount
+    rain_acidity: u8,
+    gold_amt: usize,
+    coolant_amt: usize, // Canisters buried in the dirt
+    boss: bool,
+    objective: Objective,
+    text: &'static [u8],
+}
+impl LVlSettings {
+    fn new() -> Self {
+        Self {
+            drone_limit: 0,
+            fly_limit: 0,
+            slider_limit: 0,
+            seeker_limit: 0,
+            bomber_limit: 0,
+            ufo_limit: 0,
+            turret_limit: 0,
+            worm_limit: 0,
+            drone_rte: 100,
+            ufo_rte: 600,
+            rain_chance_rte: 100,
+            rain_amount_rte: 200,
+            rain_acidity: 50,
+            gold_amt: 10,
+            coolant_amt: 2,
+            boss: false,
+            objective: Objective::Door,
+            text: b"",
+        }
+    }
+    fn apply_difficulty(&mut self, diff: &DiffProfile) {
+        let limit = |l: usize| l * diff.enemy_scale as usize / 100;
+        let rate = |r: u16, scale: u16| (r as u32 * scale as u32 / 100).clamp(1, 999) as u16;
+        self.drone_limit = limit(self.drone_limit);
+        self.fly_limit = limit(self.fly_limit);
+        self.slider_limit = limit(self.slider_limit);
+        self.seeker_limit = limit(self.seeker_limit);
+        self.bomber_limit = limit(self.bomber_limit);
+        self.ufo_limit = limit(self.ufo_limit);
+        self.turret_limit = limit(self.turret_limit);
+        self.worm_limit = limit(self.worm_limit);
+        self.drone_rte = rate(self.drone_rte, diff.spawn_scale);
+        self.ufo_rte = rate(self.ufo_rte, diff.spawn_scale);
+        self.rain_chance_rte = rate(self.rain_chance_rte, diff.rain_scale);
+        self.rain_amount_rte = rate(self.rain_amount_rte, diff.rain_scale);
+    }
+
+    // Nothing but dirt and gold
+    fn apply_sandbox(&mut self) {
+        self.drone_limit = 0;
+        self.fly_limit = 0;
+        self.slider_limit = 0;
+        self.seeker_limit = 0;
+        self.bomber_limit = 0;
+        self.ufo_limit = 0;
+        self.turret_limit = 0;
+        self.worm_limit = 0;
+        self.rain_chance_rte = 999;
+        self.rain_acidity = 0;
+        self.boss = false;
+        self.objective = Objective::Door;
+    }
+
+    // Scale a day's settings by the director's tension, each step is 25%
+    // Only drones and ufos spawn mid-day, the rest are placed once by world_gen
+    fn apply_tension(&mut self, base: &LVlSettings, tension: i8) {
+        let up = (4 + tension.max(0)) as usize;
+        let down = (4 - tension.min(0)) as usize;
+        let limit = |l: usize| l * up / down;
+        let rate = |r: u16| (r as usize * down / up).clamp(1, 999) as u16;
+        self.drone_limit = limit(base.drone_limit);
+        self.ufo_limit = limit(base.ufo_limit);
+        self.drone_rte = rate(base.drone_rte);
+        self.ufo_rte = rate(base.ufo_rte);
+        self.rain_chance_rte = rate(base.rain_chance_rte);
+        self.rain_amount_rte = rate(base.rain_amount_rte);
+    }
+}
+
+const LVLS: [LVlSettings; MAX_LVL] = [
+    // Zero is for special modes
+    LVlSettings {
+        drone_limit: 0,
         fly_limit: 0,
         slider_limit: 0,
         seeker_limit: 0,
         bomber_limit: 0,
+        ufo_limit: 0,
+        turret_limit: 0,
+        worm_limit: 0,
         drone_rte: 100,
+        ufo_rte: 600,
         rain_chance_rte: 1000,
         rain_amount_rte: 1000,
         rain_acidity: 0,
         gold_amt: 8,
+        coolant_amt: 0,
+        boss: false,
+        objective: Objective::Door,
         text: b"First
 time
 huh?",
@@ -688,11 +1661,18 @@ huh?",
         slider_limit: 0,
         seeker_limit: 0,
         bomber_limit: 1,
+        ufo_limit: 0,
+        turret_limit: 0,
+        worm_limit: 0,
         drone_rte: 100,
+        ufo_rte: 600,
         rain_chance_rte: 600,
         rain_amount_rte: 600,
         rain_acidity: 0,
         gold_amt: 8,
+        coolant_amt: 1,
+        boss: false,
+        objective: Objective::Door,
         text: b"\x84\x87\x85
 MOVE
 \x84\x87\x86\x85+\x80
@@ -704,11 +1684,18 @@ DRILL",
         slider_limit: 0,
         seeker_limit: 0,
         bomber_limit: 2,
+        ufo_limit: 0,
+        turret_limit: 0,
+        worm_limit: 0,
         drone_rte: 250,
+        ufo_rte: 600,
         rain_chance_rte: 300,
         rain_amount_rte: 300,
         rain_acidity: 5,
         gold_amt: 24,
+        coolant_amt: 1,
+        boss: false,
+        objective: Objective::Gold(6),
         text: b"Its's all
 down from
 here...",
@@ -719,11 +1706,18 @@ here...",
         slider_limit: 3,
         seeker_limit: 0,
         bomber_limit: 3,
+        ufo_limit: 0,
+        turret_limit: 0,
+        worm_limit: 0,
         drone_rte: 200,
+        ufo_rte: 600,
         rain_chance_rte: 200,
         rain_amount_rte: 300,
         rain_acidity: 10,
         gold_amt: 32,
+        coolant_amt: 2,
+        boss: false,
+        objective: Objective::Bombers,
         text: b"Slump
 day
 ...",
@@ -734,11 +1728,18 @@ day
         slider_limit: 2,
         seeker_limit: 0,
         bomber_limit: 1,
+        ufo_limit: 0,
+        turret_limit: 1,
+        worm_limit: 0,
         drone_rte: 150,
+        ufo_rte: 600,
         rain_chance_rte: 100,
         rain_amount_rte: 140,
         rain_acidity: 20,
         gold_amt: 48,
+        coolant_amt: 2,
+        boss: false,
+        objective: Objective::Survive(60),
         text: b"Who's
 drones
 are
@@ -750,11 +1751,18 @@ these?",
         slider_limit: 3,
         seeker_limit: 3,
         bomber_limit: 2,
+        ufo_limit: 1,
+        turret_limit: 1,
+        worm_limit: 1,
         drone_rte: 120,
+        ufo_rte: 600,
         rain_chance_rte: 60,
         rain_amount_rte: 120,
         rain_acidity: 30,
         gold_amt: 64,
+        coolant_amt: 2,
+        boss: false,
+        objective: Objective::Canary,
         text: b"To feel
 unwanted
 is to feel
@@ -766,11 +1774,18 @@ unstoppable",
         slider_limit: 3,
         seeker_limit: 1,
         bomber_limit: 4,
+        ufo_limit: 1,
+        turret_limit: 2,
+        worm_limit: 1,
         drone_rte: 100,
+        ufo_rte: 500,
         rain_chance_rte: 50,
         rain_amount_rte: 100,
         rain_acidity: 40,
         gold_amt: 64,
+        coolant_amt: 3,
+        boss: false,
+        objective: Objective::Depth(140),
         text: b"I never
 want to 
 see you 
@@ -783,23 +1798,387 @@ acid rain",
         slider_limit: 7,
         seeker_limit: 3,
         bomber_limit: 4,
+        ufo_limit: 2,
+        turret_limit: 2,
+        worm_limit: 1,
         drone_rte: 50,
+        ufo_rte: 400,
         rain_chance_rte: 40,
         rain_amount_rte: 80,
         rain_acidity: 60,
         gold_amt: 64,
+        coolant_amt: 3,
+        boss: true,
+        objective: Objective::Door,
         text: b"THE END?",
     },
 ];
 
 #[derive(Copy, Clone, PartialEq, Debug)]
 enum PowerUp {
-    None,
     SuperDrill,
     Invincible,
     Magnet,
+    Umbrella,   // Rain can't hurt
+    XRay,       // See gold buried far away
+    Freeze,     // Enemies stop moving
+    Jetpack,    // Hold up to fly
+    HeatSink,   // Drill doesn't heat up
+    DoubleGold, // Gold pickups count twice
+}
+const POWERUP_TYPES: [PowerUp; 9] = [
+    PowerUp::SuperDrill,
+    PowerUp::Invincible,
+    PowerUp::Magnet,
+    PowerUp::Umbrella,
+    PowerUp::XRay,
+    PowerUp::Freeze,
+    PowerUp::Jetpack,
+    PowerUp::HeatSink,
+    PowerUp::DoubleGold,
+];
+impl PowerUp {
+    fn name(&self) -> &'static str {
+        match self {
+            PowerUp::SuperDrill => "SDRILL",
+            PowerUp::Invincible => "INVINC",
+            PowerUp::Magnet => "MAGNET",
+            PowerUp::Umbrella => "UMBREL",
+            PowerUp::XRay => "X-RAY",
+            PowerUp::Freeze => "FREEZE",
+            PowerUp::Jetpack => "JETPAK",
+            PowerUp::HeatSink => "HSINK",
+            PowerUp::DoubleGold => "2XGOLD",
+        }
+    }
+    // Chance of turning up on a day, relative to the others
+    fn weight(&self, day: usize) -> u32 {
+        let day = day as u32;
+        match self {
+            PowerUp::SuperDrill => 10,
+            PowerUp::Invincible => 4 + day,
+            PowerUp::Magnet => 10,
+            PowerUp::Umbrella => 6 + day * 2,
+            PowerUp::XRay => 8,
+            PowerUp::Freeze => 2 + day,
+            PowerUp::Jetpack => 6,
+            PowerUp::HeatSink => 8,
+            PowerUp::DoubleGold => 16u32.saturating_sub(day * 2),
+        }
+    }
+    // Percent of the difficulty's powerup duration
+    fn duration(&self) -> u32 {
+        match self {
+            PowerUp::Freeze => 50,
+            PowerUp::Jetpack => 150,
+            _ => 100,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Enemy {
+    Drone,
+    Fly,
+    Slider,
+    Seeker,
+    Bomber,
+    Ufo,
+    Turret,
+    Worm,
+}
+const ENEMY_TYPES: [Enemy; 8] = [
+    Enemy::Drone,
+    Enemy::Fly,
+    Enemy::Slider,
+    Enemy::Seeker,
+    Enemy::Bomber,
+    Enemy::Ufo,
+    Enemy::Turret,
+    Enemy::Worm,
+];
+impl Enemy {
+    // Half hearts taken on contact
+    fn dmg(&self) -> u8 {
+        match self {
+            Enemy::Fly => 1,
+            Enemy::Ufo => 1,
+            Enemy::Bomber => 4,
+            _ => 2,
+        }
+    }
+    // Used for damage text
+    fn name(&self) -> &'static str {
+        match self {
+            Enemy::Drone => "drone",
+            Enemy::Fly => "fly",
+            Enemy::Slider => "slider",
+            Enemy::Seeker => "seeker",
+            Enemy::Bomber => "bomber",
+            Enemy::Ufo => "ufo",
+            Enemy::Turret => "turret",
+            Enemy::Worm => "worm",
+        }
+    }
+    // Hitbox size
+    fn size(&self) -> Pos {
+        match self {
+            Enemy::Seeker | Enemy::Bomber | Enemy::Turret => Pos::new(8, 8),
+            Enemy::Ufo => Pos::new(8, 6),
+            Enemy::Worm => Pos::new(6, 6),
+            _ => Pos::new(8, 4),
+        }
+    }
+    fn max_hp(&self) -> u8 {
+        match self {
+            Enemy::Fly => 1,
+            Enemy::Drone | Enemy::Slider | Enemy::Bomber => 2,
+            Enemy::Seeker | Enemy::Ufo | Enemy::Turret => 3,
+            Enemy::Worm => 5,
+        }
+    }
+}
+
+// Lives alongside each enemy position list
+#[derive(Copy, Clone)]
+struct Health {
+    hp: u8,
+    hit_frames: u8, // Flash and ignore hits while > 0
+}
+impl Health {
+    fn new(hp: u8) -> Self {
+        Self { hp, hit_frames: 0 }
+    }
+}
+
+// What has to be done before the door opens
+#[derive(Copy, Clone, PartialEq)]
+enum Objective {
+    Door,
+    Gold(u16),    // Collected today
+    Survive(u16), // Seconds
+    Depth(i16),   // Reach this far down, the door sits up top
+    Bombers,      // Pop every one
+    Canary,       // Bring it to the door alive
+}
+
+impl Objective {
+    fn name(&self) -> String {
+        match self {
+            Objective::Door => "FIND THE DOOR".to_string(),
+            Objective::Gold(n) => format!("GET {} GOLD", n),
+            Objective::Survive(t) => format!("SURVIVE {}S", t),
+            Objective::Depth(y) => format!("DIG TO {}", y),
+            Objective::Bombers => "POP BOMBERS".to_string(),
+            Objective::Canary => "SAVE CANARY".to_string(),
+        }
+    }
+}
+
+// Follows the player's path, the rain is bad for it
+struct Canary {
+    trail: Vec<Pos>, // Past player positions, newest first
+    hp: u8,
+    hit_frames: u8,
+}
+impl Canary {
+    fn new(pos: Pos) -> Self {
+        Self {
+            trail: vec![pos; CANARY_LAG],
+            hp: CANARY_HP,
+            hit_frames: 0,
+        }
+    }
+    fn pos(&self) -> Pos {
+        self.trail[CANARY_LAG - 1]
+    }
+}
+
+#[derive(Copy, Clone)]
+struct Boss {
+    pos: Pos,
+    hp: u16,
+    timer: u16, // Frames until the next attack
+    hit_frames: u8,
+}
+impl Boss {
+    // Gets meaner as it loses health
+    fn phase(&self) -> u8 {
+        if self.hp > BOSS_HP * 2 / 3 {
+            0
+        } else if self.hp > BOSS_HP / 3 {
+            1
+        } else {
+            2
+        }
+    }
+}
+
+// Segment chain for a worm, the head lives in `worm_locs`
+struct WormBody {
+    trail: Vec<Pos>, // Past head positions, newest first
+    dir: Pos,        // Heading while wandering
+}
+impl WormBody {
+    fn new(head: Pos) -> Self {
+        Self {
+            trail: vec![head; WORM_LEN * WORM_GAP],
+            dir: Pos::new(1, 0),
+        }
+    }
+    // Every few steps along the trail there is a segment
+    fn segments(&self) -> impl Iterator<Item = &Pos> {
+        self.trail.iter().skip(WORM_GAP - 1).step_by(WORM_GAP)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum DmgType {
+    Acid,
+    Impact,
+    Blast,
+    Heat,
+}
+
+// Armour pieces are bought in this order, each one softens a damage type
+const ARMOUR: [(&str, DmgType); 3] = [
+    ("ACID COAT", DmgType::Acid),
+    ("HELMET", DmgType::Impact),
+    ("BLAST VEST", DmgType::Blast),
+];
+
+// Everything that can hurt the player
+#[derive(Copy, Clone)]
+enum DmgSrc {
+    Rain,
+    Bomb,
+    Boss,
+    Dirt,
+    Heat,
+    Enemy(Enemy),
+    Shot(Shooter),
+}
+impl DmgSrc {
+    fn name(&self) -> &'static str {
+        match self {
+            DmgSrc::Rain => "rain",
+            DmgSrc::Bomb => "bomb",
+            DmgSrc::Boss => "boss",
+            DmgSrc::Dirt => "dirt",
+            DmgSrc::Heat => "drill",
+            DmgSrc::Enemy(kind) => kind.name(),
+            DmgSrc::Shot(shooter) => shooter.name(),
+        }
+    }
+    // Half hearts
+    fn amount(&self) -> u8 {
+        match self {
+            DmgSrc::Rain => 1,
+            DmgSrc::Bomb => 3,
+            DmgSrc::Boss => 3,
+            DmgSrc::Dirt => 2,
+            DmgSrc::Heat => 1,
+            DmgSrc::Enemy(kind) => kind.dmg(),
+            DmgSrc::Shot(Shooter::Boss) => 2,
+            DmgSrc::Shot(_) => 1,
+        }
+    }
+    fn kind(&self) -> DmgType {
+        match self {
+            DmgSrc::Rain | DmgSrc::Shot(_) => DmgType::Acid,
+            DmgSrc::Bomb | DmgSrc::Enemy(Enemy::Bomber) => DmgType::Blast,
+            DmgSrc::Heat => DmgType::Heat,
+            _ => DmgType::Impact,
+        }
+    }
+}
+
+// Whoever fired a hostile projectile
+#[derive(Copy, Clone)]
+enum Shooter {
+    Enemy(Enemy),
+    Boss,
+}
+impl Shooter {
+    fn name(&self) -> &'static str {
+        match self {
+            Shooter::Enemy(kind) => kind.name(),
+            Shooter::Boss => "boss",
+        }
+    }
+}
+
+// Pooled, a slot with no life left is free to reuse
+#[derive(Copy, Clone)]
+struct Projectile {
+    x: i16, // 1/8 pixels
+    y: i16,
+    vx: i8, // 1/8 pixels per frame
+    vy: i8,
+    life: u8, // Frames left
+    dig: u8,  // Radius of dirt eaten on impact
+    owner: Shooter,
+}
+impl Projectile {
+    fn pos(&self) -> Pos {
+        Pos::new(self.x / PROJ_SUB, self.y / PROJ_SUB)
+    }
+}
+
+// Watches how the player is doing and pushes the day harder or softer
+#[derive(Copy, Clone)]
+struct Director {
+    tension: i8,   // Negative is easier, positive is harder
+    dmg_mark: u16, // Stats at the last review
+    gold_mark: u16,
+    overheats: u8, // Since the last review
+}
+impl Director {
+    fn new() -> Director {
+        Director {
+            tension: 0,
+            dmg_mark: 0,
+            gold_mark: 0,
+            overheats: 0,
+        }
+    }
+}
+
+// Kept on disk between runs
+#[derive(Copy, Clone)]
+struct Save {
+    gold: u32,         // Lifetime gold from arcade runs
+    achievements: u16, // Bit per ACHIEVEMENT_TYPES
+}
+impl Save {
+    fn new() -> Self {
+        Self {
+            gold: 0,
+            achievements: 0,
+        }
+    }
+
+    fn load() -> Self {
+        let mut buf = [0u8; SAVE_SIZE];
+        unsafe { diskr(buf.as_mut_ptr(), SAVE_SIZE as u32) };
+        // Blank or someone else's disk
+        if buf[0..3] != SAVE_MAGIC {
+            return Self::new();
+        }
+        Self {
+            gold: u32::from_le_bytes([buf[4], buf[5], buf[6], buf[7]]),
+            achievements: u16::from_le_bytes([buf[8], buf[9]]),
+        }
+    }
+
+    fn store(&self) {
+        let mut buf = [0u8; SAVE_SIZE];
+        buf[0..3].copy_from_slice(&SAVE_MAGIC);
+        buf[4..8].copy_from_slice(&self.gold.to_le_bytes());
+        buf[8..10].copy_from_slice(&self.achievements.to_le_bytes());
+        unsafe { diskw(buf.as_ptr(), SAVE_SIZE as u32) };
+    }
 }
-const POWERUP_TYPES: [PowerUp; 3] = [PowerUp::SuperDrill, PowerUp::Invincible, PowerUp::Magnet];
 
 #[derive(Copy, Clone)]
 struct Stats {
@@ -809,6 +2188,7 @@ struct Stats {
     survived: u16,
     dmg: u16,
     powerups: u16,
+    overheats: u16,
 }
 impl Stats {
     fn new() -> Self {
@@ -819,6 +2199,7 @@ impl Stats {
             survived: 0,
             dmg: 0,
             powerups: 0,
+            overheats: 0,
         }
     }
 }
@@ -838,8 +2219,16 @@ struct GameMaster {
     door_loc: Pos,
     powerup_loc: Pos,
     powerup_taken: bool,
-    powerup_cur: PowerUp,
-    powerup_frames: u16,
+    powerup_frames: [u16; POWERUP_TYPES.len()], // Frames left for each type
+    held: Vec<PowerUp>,                         // Banked powerups, oldest first
+    held_slots: u8,
+    held_bought: u8, // Newest held entries bought this shop visit
+    perks: u8,       // Bit per PERK_TYPES taken this run
+    mutators: u8,    // Bit per MUTATOR_TYPES on for this run
+    mutator_sel: usize,
+    perk_offer: Vec<Perk>,
+    perk_sel: usize,
+    rain_coat_used: bool,
     gold_locs: Vec<Pos>,
     gold: u16,
     drill_speed: u8,
@@ -848,6 +2237,9 @@ struct GameMaster {
     drill_overheat: bool,
     auto_drill: bool,
     cur_lvl_data: LVlSettings,
+    lvl_base: LVlSettings, // Day settings before the director touches them
+    director_on: bool,
+    director: Director,
     rain_locs: Vec<Pos>,
     drone_locs: Vec<Pos>,
     fly_locs: Vec<Pos>,
@@ -855,19 +2247,81 @@ struct GameMaster {
     seeker_locs: Vec<Pos>,
     bomber_locs: Vec<Pos>,
     bomber_times: Vec<u16>,
+    ufo_locs: Vec<Pos>,
+    ufo_times: Vec<u16>,
+    seeker_alert: Vec<u16>,
+    turret_locs: Vec<Pos>,
+    turret_times: Vec<u16>,
+    projectiles: Vec<Projectile>,
+    worm_locs: Vec<Pos>,
+    worm_bodies: Vec<WormBody>,
+    vibration: Pos, // Where drilling was last heard
+    vibration_frames: u16,
+    fast_pass: bool, // Extra FastEnemies update, only moves enemies
+    boss: Option<Boss>,
+    drone_hp: Vec<Health>,
+    fly_hp: Vec<Health>,
+    slider_hp: Vec<Health>,
+    seeker_hp: Vec<Health>,
+    bomber_hp: Vec<Health>,
+    ufo_hp: Vec<Health>,
+    turret_hp: Vec<Health>,
+    worm_hp: Vec<Health>,
+    coolant_locs: Vec<Pos>,
+    fx_locs: Vec<Pos>,
+    fx_times: Vec<u8>,
+    drill_boxes: Vec<(Pos, Pos)>,
+    drill_tree: u8, // Bit per DRILL_TREE upgrade owned
+    workshop_sel: usize,
+    path_field: Vec<u8>,
+    path_queue: Vec<u16>, // Reused by up_paths, carried over by world_reset
+    bombs: u8,
+    bomb_locs: Vec<Pos>,
+    bomb_times: Vec<u16>,
     wind_speed: i8,
     player_flags_last: u32,
+    gamepad_last: u8,
     dmg_frames: u8,
+    inv_frames: u8,
+    stun_frames: u8,
+    vent_cooldown: u16,
+    crush_mass: u16,   // Dirt piling onto the player
+    cavein_stress: u8, // Drilling under an overhang
+    cavein_timer: u8,  // Rumbling before a cave-in
+    armour: u8,        // Pieces of ARMOUR owned
     no_input_frames: u8,
     has_drilled: bool,
     is_drilling: bool,
     screen: Screen,
-    cost_heart: u16,
-    cost_drill_speed: u16,
-    cost_drill_cool: u16,
-    purchased: u8,
+    shop_stock: Vec<usize>, // Indexes into SHOP_CATALOGUE
+    shop_sel: usize,
+    shop_bought: [u8; SHOP_CATALOGUE.len()], // Times each item was bought this run
+    shop_rerolls: u8,
+    shop_msg: &'static str,
+    purchased: u8, // Catalogue index + 1 of the last item bought or sold
     gameover_acc: u8,
     pal_index: usize,
+    loadout: usize, // Index into LOADOUTS for the next run
+    miner: usize,   // Index into MINERS
+    coolant_extra: u8,
+    save: Save,
+    toasts: Vec<Achievement>, // Waiting to be shown, first is on screen
+    toast_frames: u16,
+    day_bombers: u8, // Bombers popped today
+    day_hurt: bool,
+    day_sky_drilled: bool, // Cleared a cell above DIRT_START today
+    lives_on: bool,        // Dying restarts the day while lives last
+    lives: u8,
+    continues: u8, // Bought this run, each costs more
+    continue_frames: u16,
+    respawn_frames: u16,
+    respawn_gold: u16, // Dropped by the last death
+    day_gold: u16,
+    day_nuggets: usize, // World gen gold still out, always at the front of gold_locs
+    day_nuggets_got: usize,
+    day_frames: u32,
+    day_depth: i16, // Deepest the player got today
+    canary: Option<Canary>,
     last_dmg_from: String,
     door_timer: u16,
     stats: Stats,
@@ -892,8 +2346,16 @@ impl GameMaster {
             door_loc: Pos { x: 0, y: 0 },
             powerup_loc: Pos { x: 0, y: 0 },
             powerup_taken: false,
-            powerup_cur: PowerUp::None,
-            powerup_frames: 0,
+            powerup_frames: [0; POWERUP_TYPES.len()],
+            held: Vec::new(),
+            held_slots: 1,
+            held_bought: 0, // Not carried over, so it resets before each shop
+            perks: 0,
+            mutators: 0,
+            mutator_sel: 0,
+            perk_offer: Vec::new(),
+            perk_sel: 0,
+            rain_coat_used: false,
             gold_locs: Vec::new(),
             gold: 0,
             drill_speed: 48,
@@ -902,6 +2364,9 @@ impl GameMaster {
             drill_overheat: false,
             auto_drill: false,
             cur_lvl_data: LVlSettings::new(),
+            lvl_base: LVlSettings::new(),
+            director_on: false,
+            director: Director::new(),
             rain_locs: Vec::new(),
             drone_locs: Vec::new(),
             fly_locs: Vec::new(),
@@ -909,19 +2374,81 @@ impl GameMaster {
             seeker_locs: Vec::new(),
             bomber_locs: Vec::new(),
             bomber_times: Vec::new(),
+            ufo_locs: Vec::new(),
+            ufo_times: Vec::new(),
+            seeker_alert: Vec::new(),
+            turret_locs: Vec::new(),
+            turret_times: Vec::new(),
+            projectiles: Vec::new(),
+            worm_locs: Vec::new(),
+            worm_bodies: Vec::new(),
+            vibration: Pos::new(0, 0),
+            vibration_frames: 0,
+            fast_pass: false,
+            boss: None,
+            drone_hp: Vec::new(),
+            fly_hp: Vec::new(),
+            slider_hp: Vec::new(),
+            seeker_hp: Vec::new(),
+            bomber_hp: Vec::new(),
+            ufo_hp: Vec::new(),
+            turret_hp: Vec::new(),
+            worm_hp: Vec::new(),
+            coolant_locs: Vec::new(),
+            fx_locs: Vec::new(),
+            fx_times: Vec::new(),
+            drill_boxes: Vec::new(),
+            drill_tree: 0,
+            workshop_sel: 0,
+            path_field: Vec::new(),
+            path_queue: Vec::new(), // Grows once in up_paths
+            bombs: 0,
+            bomb_locs: Vec::new(),
+            bomb_times: Vec::new(),
             wind_speed: 0,
             player_flags_last: BLIT_1BPP,
+            gamepad_last: 0,
             dmg_frames: 0,
+            inv_frames: 0,
+            stun_frames: 0,
+            vent_cooldown: 0,
+            crush_mass: 0,
+            cavein_stress: 0,
+            cavein_timer: 0,
+            armour: 0,
             no_input_frames: 0,
             has_drilled: false,
             is_drilling: false,
             screen: Screen::Intro,
-            cost_heart: 8,
-            cost_drill_speed: 16,
-            cost_drill_cool: 16,
-            purchased: 0, // None, heart, drill speed, drill cool
+            shop_stock: Vec::new(),
+            shop_sel: 0,
+            shop_bought: [0; SHOP_CATALOGUE.len()],
+            shop_rerolls: 0,
+            shop_msg: "",
+            purchased: 0,
             gameover_acc: 0,
             pal_index: 0,
+            loadout: 0,
+            miner: 0,
+            coolant_extra: 0,
+            save: Save::new(), // Loaded once in start()
+            toasts: Vec::new(),
+            toast_frames: 0,
+            day_bombers: 0,
+            day_hurt: false,
+            day_sky_drilled: false,
+            lives_on: false,
+            lives: 0,
+            continues: 0,
+            continue_frames: 0,
+            respawn_frames: 0,
+            respawn_gold: 0,
+            day_gold: 0,
+            day_nuggets: 0,
+            day_nuggets_got: 0,
+            day_frames: 0,
+            day_depth: 0,
+            canary: None,
             last_dmg_from: String::new(),
             door_timer: 0,
             stats: Stats::new(),
@@ -936,18 +2463,33 @@ impl GameMaster {
         (gamepad & check) != 0
     }
 
-    fn input_check_any(&mut self) -> bool {
+    // Only true on the frame the button goes down
+    fn input_pressed(&mut self, check: u8) -> bool {
         if self.no_input_frames > 0 {
             return false;
         }
         let gamepad = unsafe { *GAMEPAD1 };
-        gamepad != 0
+        (gamepad & check) != 0 && (self.gamepad_last & check) == 0
     }
 
-    fn input_main(&mut self) {
+    fn input_check_any(&mut self) -> bool {
+        if self.no_input_frames > 0 {
+            return false;
+        }
+        let gamepad = unsafe { *GAMEPAD1 };
+        gamepad != 0
+    }
+
+    fn input_main(&mut self) {
         if self.hp < 1 && !self.invincible {
             return;
         }
+        // Stunned players just fall
+        if self.stun_frames > 0 {
+            self.stun_frames -= 1;
+            self.player_fall();
+            return;
+        }
         let pos_cache = self.player_pos;
         let mut drill_on = false;
         if self.input_check(BUTTON_1) || self.auto_drill {
@@ -968,57 +2510,79 @@ impl GameMaster {
             lr = 2;
         }
         self.is_drilling = false;
-        if drill_on && self.input_check(BUTTON_DOWN) {
-            self.is_drilling = true;
-            self.has_drilled = true;
+        // Areas where the drill bit can hit enemies this frame
+        self.drill_boxes.clear();
+        let (px, py) = (self.player_pos.x, self.player_pos.y);
+        let ps = PLAYER_SIZE as i16;
+        let vertical = if self.input_check(BUTTON_DOWN) {
+            1
+        } else if self.input_check(BUTTON_UP) {
+            -1
+        } else {
+            0
+        };
+        if drill_on && vertical == 1 {
             self.dir = 3 + lr;
-            // Remove the 4 blocks under the smiley
-            self.world_drill_area(
-                (self.player_pos.x - 1) as usize,
-                (self.player_pos.y + PLAYER_SIZE as i16) as usize,
-                (PLAYER_SIZE + 2) as usize,
-                1,
-                self.drill_speed,
+            // Remove the blocks under the smiley
+            self.drill(
+                (Pos::new(px - 1, py + ps), Pos::new(ps + 2, 1)),
+                (Pos::new(px - 1, py + ps), Pos::new(ps + 2, 6)),
+                Pos::new(0, 1),
             );
-        } else if drill_on && self.input_check(BUTTON_UP) {
-            self.is_drilling = true;
-            self.has_drilled = true;
+        } else if drill_on && vertical == -1 {
             self.dir = 6 + lr;
-            // Remove the 4 blocks above the smiley
-            self.world_drill_area(
-                (self.player_pos.x - 1) as usize,
-                (self.player_pos.y - 5) as usize,
-                (PLAYER_SIZE + 2) as usize,
-                5,
-                self.drill_speed,
+            // Remove the blocks above the smiley
+            self.drill(
+                (Pos::new(px - 1, py - 5), Pos::new(ps + 2, 5)),
+                (Pos::new(px - 1, py - 6), Pos::new(ps + 2, 6)),
+                Pos::new(0, -1),
             );
         }
+        if drill_on && vertical != 0 && lr > 0 && self.drill_owns(DrillPart::Diagonal) {
+            // Clip the corner we're heading for
+            let cx = if lr == 2 { px + ps } else { px - 4 };
+            let cy = if vertical == 1 { py + ps } else { py - 4 };
+            let corner = (Pos::new(cx, cy), Pos::new(4, 4));
+            let dir = Pos::new(if lr == 2 { 1 } else { -1 }, vertical);
+            self.drill(corner, corner, dir);
+        }
 
         if drill_on && self.input_check(BUTTON_RIGHT) {
-            self.is_drilling = true;
-            self.has_drilled = true;
-            // Remove the 4 blocks to the right of the smiley
-            self.world_drill_area(
-                (self.player_pos.x + PLAYER_SIZE as i16 - 1) as usize,
-                (self.player_pos.y - 1) as usize,
-                1,
-                (PLAYER_SIZE + 1) as usize,
-                self.drill_speed,
+            // Remove the blocks to the right of the smiley
+            self.drill(
+                (Pos::new(px + ps - 1, py - 1), Pos::new(1, ps + 1)),
+                (Pos::new(px + ps, py - 1), Pos::new(6, ps + 2)),
+                Pos::new(1, 0),
             );
         }
         if drill_on && self.input_check(BUTTON_LEFT) {
-            self.is_drilling = true;
-            self.has_drilled = true;
-            // Remove the 4 blocks to the left of the smiley
-            self.world_drill_area(
-                (self.player_pos.x) as usize,
-                (self.player_pos.y - 1) as usize,
-                1,
-                (PLAYER_SIZE + 1) as usize,
-                self.drill_speed,
+            // Remove the blocks to the left of the smiley
+            self.drill(
+                (Pos::new(px, py - 1), Pos::new(1, ps + 1)),
+                (Pos::new(px - 6, py - 1), Pos::new(6, ps + 2)),
+                Pos::new(-1, 0),
             );
         }
+        // Jetpack beats gravity while not drilling
+        if !drill_on && self.powerup_on(PowerUp::Jetpack) && self.input_check(BUTTON_UP) {
+            self.player_pos.y = (self.player_pos.y - 2).max(0);
+        }
         self.player_collide_world(pos_cache);
+        self.player_fall();
+
+        // Drop a bomb with down, use a held powerup with up, otherwise vent the drill
+        if self.input_pressed(BUTTON_2) {
+            if self.input_check(BUTTON_DOWN) {
+                self.bomb_drop();
+            } else if self.input_check(BUTTON_UP) {
+                self.powerup_use();
+            } else {
+                self.drill_vent();
+            }
+        }
+    }
+
+    fn player_fall(&mut self) {
         let pos_cache = self.player_pos;
         self.player_pos.y += 1;
         if self.player_pos.y > (WORLD_SIZE - PLAYER_SIZE as usize) as i16 {
@@ -1028,6 +2592,110 @@ impl GameMaster {
         self.player_wrap();
     }
 
+    // Dump all the drill heat as steam, the hotter it was the more it scalds
+    fn drill_vent(&mut self) {
+        if self.vent_cooldown > 0 || self.drill_heat == 0 {
+            self.sfx_deny();
+            return;
+        }
+        let heat = self.drill_heat as f32 / self.drill_heat_max as f32;
+        let center = Pos::new(self.player_pos.x + 4, self.player_pos.y + 4);
+        let r = 8. + heat * 16.;
+        self.blast_enemies(&center, r, 1 + (heat * 2.) as u8);
+        self.drill_heat = 0;
+        self.drill_overheat = false;
+        self.vent_cooldown = VENT_COOLDOWN;
+        self.fx_locs.push(self.player_pos);
+        self.fx_times.push(FX_FRAMES);
+        self.sfx_vent();
+        trace(format!("VENT: heat={} r={}", heat, r));
+    }
+
+    // Drill out an area and let the bit hit enemies in a box, both grown by upgrades
+    fn drill(&mut self, area: (Pos, Pos), hit: (Pos, Pos), dir: Pos) {
+        self.is_drilling = true;
+        self.has_drilled = true;
+        let pierce = if self.drill_owns(DrillPart::Pierce) {
+            8
+        } else {
+            0
+        };
+        let hit = self.drill_grow(hit, dir, pierce);
+        self.drill_boxes.push(hit);
+        let (mut pos, mut size) = self.drill_grow(area, dir, 0);
+        // Keep the area inside the world
+        if pos.x < 0 {
+            size.x += pos.x;
+            pos.x = 0;
+        }
+        if pos.y < 0 {
+            size.y += pos.y;
+            pos.y = 0;
+        }
+        if size.x <= 0 || size.y <= 0 {
+            return;
+        }
+        self.world_drill_area(
+            pos.x as usize,
+            pos.y as usize,
+            size.x as usize,
+            size.y as usize,
+            self.drill_speed,
+        );
+    }
+
+    // Widen across the drilling direction and lengthen along it
+    fn drill_grow(&self, rect: (Pos, Pos), dir: Pos, extra: i16) -> (Pos, Pos) {
+        let (mut pos, mut size) = rect;
+        let wide = self.drill_level(DrillPart::Wide) as i16 * 2;
+        let reach = self.drill_level(DrillPart::Reach) as i16 * 3 + extra;
+        if dir.x == 0 {
+            pos.x -= wide;
+            size.x += wide * 2;
+        }
+        if dir.y == 0 {
+            pos.y -= wide;
+            size.y += wide * 2;
+        }
+        if dir.x != 0 {
+            size.x += reach;
+            if dir.x < 0 {
+                pos.x -= reach;
+            }
+        }
+        if dir.y != 0 {
+            size.y += reach;
+            if dir.y < 0 {
+                pos.y -= reach;
+            }
+        }
+        (pos, size)
+    }
+
+    fn drill_owns(&self, part: DrillPart) -> bool {
+        self.drill_level(part) > 0
+    }
+
+    fn drill_level(&self, part: DrillPart) -> u8 {
+        let mut level = 0;
+        for (i, up) in DRILL_TREE.iter().enumerate() {
+            if up.part == part && self.drill_tree & (1 << i) != 0 {
+                level += 1;
+            }
+        }
+        level
+    }
+
+    // Damage the drill does to enemies
+    fn drill_dmg(&self) -> u8 {
+        let hot = self.passive_has(Passive::HotDrill) as u8;
+        if self.drill_owns(DrillPart::Pierce) {
+            2 + hot
+        } else {
+            1 + hot
+        }
+    }
+
     fn world_reset(&mut self) {
         let game_mode = self.game_mode;
         let gold = self.gold;
@@ -1038,11 +2706,32 @@ impl GameMaster {
         let hp = self.hp;
         let drill_speed = self.drill_speed;
         let drill_heat_max = self.drill_heat_max;
+        let bombs = self.bombs;
         let pal_index = self.pal_index;
         let stats = self.stats;
+        let armour = self.armour;
+        let drill_tree = self.drill_tree;
+        let held = self.held.clone();
+        let held_slots = self.held_slots;
+        let perks = self.perks;
+        let mutators = self.mutators;
+        let shop_bought = self.shop_bought;
+        let coolant_extra = self.coolant_extra;
+        let miner = self.miner;
+        let loadout = self.loadout;
+        let lives_on = self.lives_on;
+        let lives = self.lives;
+        let continues = self.continues;
+        let save = self.save;
+        let toasts = self.toasts.clone();
+        let toast_frames = self.toast_frames;
+        let director_on = self.director_on;
+        let director = self.director;
+        let path_queue = std::mem::take(&mut self.path_queue);
 
         *self = GameMaster::new();
 
+        self.path_queue = path_queue;
         self.game_mode = game_mode;
         self.gold = gold;
         self.rng = rng;
@@ -1052,8 +2741,122 @@ impl GameMaster {
         self.hp = hp;
         self.drill_speed = drill_speed;
         self.drill_heat_max = drill_heat_max;
+        self.bombs = bombs;
         self.pal_index = pal_index;
         self.stats = stats;
+        self.armour = armour;
+        self.drill_tree = drill_tree;
+        self.held = held;
+        self.held_slots = held_slots;
+        self.perks = perks;
+        self.mutators = mutators;
+        self.shop_bought = shop_bought;
+        self.coolant_extra = coolant_extra;
+        self.miner = miner;
+        self.loadout = loadout;
+        self.lives_on = lives_on;
+        self.lives = lives;
+        self.continues = continues;
+        self.save = save;
+        self.toasts = toasts;
+        self.toast_frames = toast_frames;
+        self.director_on = director_on;
+        self.director = director;
+    }
+
+    fn achieved(&self, a: Achievement) -> bool {
+        self.save.achievements & (1 << a as u16) != 0
+    }
+
+    // Only arcade runs count, saved straight away since they're rare
+    fn achieve(&mut self, a: Achievement) {
+        if self.game_mode != 0 || self.achieved(a) {
+            return;
+        }
+        self.save.achievements |= 1 << a as u16;
+        self.save.store();
+        if self.toasts.is_empty() {
+            self.toast_frames = TOAST_FRAMES;
+        }
+        self.toasts.push(a);
+        trace(format!("ACHIEVED: {}", a.name()));
+    }
+
+    // Checks for a day just cleared through the door
+    fn achieve_day(&mut self) {
+        if !self.day_sky_drilled {
+            self.achieve(Achievement::HandsOff);
+        }
+        if self.day_nuggets_got > 0 && self.day_nuggets_got == self.cur_lvl_data.gold_amt {
+            self.achieve(Achievement::GoldRush);
+        }
+        if !self.day_hurt {
+            self.achieve(Achievement::Untouched);
+        }
+    }
+
+    fn up_toast(&mut self) {
+        if self.toasts.is_empty() {
+            return;
+        }
+        if self.toast_frames == TOAST_FRAMES {
+            self.sfx_achieve();
+        }
+        self.toast_frames = self.toast_frames.saturating_sub(1);
+        if self.toast_frames == 0 {
+            self.toasts.remove(0);
+            self.toast_frames = TOAST_FRAMES;
+        }
+    }
+
+    // Things with no UNLOCKS entry are always open
+    fn unlocked(&self, u: Unlock) -> bool {
+        UNLOCKS
+            .iter()
+            .filter(|e| e.unlock == u)
+            .all(|e| self.save.gold >= e.gold)
+    }
+
+    fn pals_open(&self) -> usize {
+        UNLOCKS
+            .iter()
+            .filter(|e| self.save.gold >= e.gold)
+            .fold(PALS_FREE, |n, e| match e.unlock {
+                Unlock::Palettes(p) => n.max(p),
+                _ => n,
+            })
+    }
+
+    fn mutator_on(&self, m: Mutator) -> bool {
+        self.mutators & (1 << m as u8) != 0
+    }
+
+    // Score is the gold collected times this, in percent
+    fn score_mult(&self) -> u16 {
+        100 + MUTATOR_TYPES
+            .iter()
+            .filter(|m| self.mutator_on(**m))
+            .map(|m| m.bonus())
+            .sum::<u16>()
+    }
+
+    fn max_hp(&self) -> u8 {
+        if self.mutator_on(Mutator::GlassMiner) {
+            2
+        } else {
+            MAX_HP
+        }
+    }
+
+    // Hearts a run starts with, and a respawn comes back with
+    fn start_hp(&self) -> u8 {
+        let kit = &LOADOUTS[self.loadout];
+        let hp = (self.diff().start_hp + kit.hp) as i8 + self.miner().hp;
+        (hp.max(2) as u8).min(self.max_hp())
+    }
+
+    fn diff(&self) -> &'static DiffProfile {
+        &DIFFS[self.difficulty as usize]
     }
 
     fn world_gen(&mut self) {
@@ -1075,11 +2878,27 @@ impl GameMaster {
             let y = self.rng.i16(DIRT_START as i16..(WORLD_SIZE as i16));
             self.gold_locs.push(Pos::new(x, y));
         }
+        self.day_nuggets = self.gold_locs.len();
+        // Coolant canisters in little pockets
+        trace("Coolant");
+        for _ in 0..self.cur_lvl_data.coolant_amt + self.coolant_extra as usize {
+            let x = self.rng.i16(0..(WORLD_SIZE as i16 - 8));
+            let y = self
+                .rng
+                .i16(DIRT_START as i16 + 16..(WORLD_SIZE as i16 - 8));
+            self.coolant_locs.push(Pos::new(x, y));
+            self.world_set_area(x as usize, y as usize, 8, 8, false);
+        }
         // Exit location
         trace("Exit");
         let exit_x = self.rng.i16(4..(WORLD_SIZE as i16 - 12));
         trace(format!("Exit: {}", exit_x));
-        self.door_loc = Pos::new(exit_x, 152);
+        // Depth days put the door on the surface to come back up to
+        let exit_y = match self.cur_lvl_data.objective {
+            Objective::Depth(_) => DIRT_START as i16 - 8,
+            _ => 152,
+        };
+        self.door_loc = Pos::new(exit_x, exit_y);
         self.world_set_area(
             (self.door_loc.x as usize).saturating_sub(4),
             (self.door_loc.y as usize).saturating_sub(2),
@@ -1089,6 +2908,7 @@ impl GameMaster {
         );
         // Powerup location
         trace("Powerup");
+        self.powerup_taken = self.mutator_on(Mutator::NoPowerups);
         let pu_x = self.rng.i16(4..(WORLD_SIZE as i16 - 12));
         // Only spawn at higher y
         let pu_y = self.rng.i16(DIRT_START as i16..(DIRT_START as i16 + 64));
@@ -1114,23 +2934,62 @@ impl GameMaster {
         // Fly locations
         trace("Flies");
         for _ in 0..self.cur_lvl_data.fly_limit {
-            self.fly_locs.push(spawn_loc(&mut self.rng));
+            let pos = spawn_loc(&mut self.rng);
+            self.enemy_spawn(Enemy::Fly, pos);
         }
         // Slider locations
         trace("Sliders");
         for _ in 0..self.cur_lvl_data.slider_limit {
-            self.slider_locs.push(spawn_loc(&mut self.rng));
+            let pos = spawn_loc(&mut self.rng);
+            self.enemy_spawn(Enemy::Slider, pos);
         }
         // Seeker locations
         trace("Seekers");
         for _ in 0..self.cur_lvl_data.seeker_limit {
-            self.seeker_locs.push(spawn_loc(&mut self.rng));
+            let mut pos = spawn_loc(&mut self.rng);
+            // Seekers don't dig so give them a pocket to sit in
+            // Line it up with the path grid so tunnels can reach it
+            pos.x -= pos.x % PATH_CELL as i16;
+            pos.y -= pos.y % PATH_CELL as i16;
+            self.world_set_area(pos.x as usize, pos.y as usize, 8, 8, false);
+            self.enemy_spawn(Enemy::Seeker, pos);
+        }
+        // Turret locations
+        trace("Turrets");
+        for _ in 0..self.cur_lvl_data.turret_limit {
+            let pos = spawn_loc(&mut self.rng);
+            // Turrets sit in a little pocket in the dirt
+            self.world_set_area(pos.x as usize, pos.y as usize, 8, 8, false);
+            self.enemy_spawn(Enemy::Turret, pos);
+        }
+        // Worm locations
+        trace("Worms");
+        for _ in 0..self.cur_lvl_data.worm_limit {
+            let pos = spawn_loc(&mut self.rng);
+            self.enemy_spawn(Enemy::Worm, pos);
+        }
+        // Boss
+        if self.cur_lvl_data.boss {
+            trace("Boss");
+            self.boss = Some(Boss {
+                // Starts deep down and digs its way up
+                pos: Pos::new(
+                    WORLD_SIZE as i16 / 2 - BOSS_SIZE / 2,
+                    WORLD_SIZE as i16 - BOSS_SIZE,
+                ),
+                hp: BOSS_HP,
+                timer: 240,
+                hit_frames: 0,
+            });
+        }
+        if self.cur_lvl_data.objective == Objective::Canary {
+            self.canary = Some(Canary::new(self.player_pos));
         }
         // Bomber locations
         trace("Bombers");
         for _ in 0..self.cur_lvl_data.bomber_limit {
-            self.bomber_locs.push(spawn_loc(&mut self.rng));
-            self.bomber_times.push(0);
+            let pos = spawn_loc(&mut self.rng);
+            self.enemy_spawn(Enemy::Bomber, pos);
         }
         // Wind speed
         self.wind_speed = self.rng.i8(5..95);
@@ -1146,6 +3005,62 @@ impl GameMaster {
         self.world.set(index, value);
     }
 
+    // Count solid cells around a cell
+    fn world_neighbors(&self, x: usize, y: usize) -> u8 {
+        let mut neighbors = 0;
+        for oy in -1..=1 {
+            for ox in -1..=1 {
+                if ox == 0 && oy == 0 {
+                    continue;
+                }
+                if let Some(ncell) =
+                    self.world_get((x as i32 + ox) as usize, (y as i32 + oy) as usize)
+                {
+                    if ncell {
+                        neighbors += 1;
+                    }
+                }
+            }
+        }
+        neighbors
+    }
+
+    // Walk the world grid from `from` to `to` (Bresenham)
+    // Returns the first solid cell on the way and its distance from `from`
+    fn world_raycast(&self, from: &Pos, to: &Pos) -> Option<(Pos, f32)> {
+        let (mut x, mut y) = (from.x as i32, from.y as i32);
+        let (x1, y1) = (to.x as i32, to.y as i32);
+        let dx = (x1 - x).abs();
+        let dy = -(y1 - y).abs();
+        let sx = if x < x1 { 1 } else { -1 };
+        let sy = if y < y1 { 1 } else { -1 };
+        let mut err = dx + dy;
+        loop {
+            let in_world = x >= 0 && y >= 0 && x < WORLD_SIZE as i32 && y < WORLD_SIZE as i32;
+            if in_world && self.world_get(x as usize, y as usize) == Some(true) {
+                let hit = Pos::new(x as i16, y as i16);
+                return Some((hit, from.distance(&hit)));
+            }
+            if x == x1 && y == y1 {
+                return None;
+            }
+            let e2 = err * 2;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+
+    // Nothing solid between two points
+    fn world_los(&self, from: &Pos, to: &Pos) -> bool {
+        self.world_raycast(from, to).is_none()
+    }
+
     fn world_set_area(&mut self, x: usize, y: usize, w: usize, h: usize, value: bool) {
         for dy in 0..h {
             for dx in 0..w {
@@ -1180,7 +3095,7 @@ impl GameMaster {
     }
 
     // Like set area but with chance
-    fn world_drill_area(&mut self, x: usize, y: usize, w: usize, h: usize, chance: u8) {
+    fn world_drill_area(&mut self, x: usize, y: usize, mut w: usize, mut h: usize, chance: u8) {
         // Prevent overflow and out-of-bounds
         if x.checked_add(w).map_or(true, |end_x| end_x > WORLD_SIZE)
             || y.checked_add(h).map_or(true, |end_y| end_y > WORLD_SIZE)
@@ -1191,8 +3106,8 @@ impl GameMaster {
             // Problems here will cause out-of-bounds panics
 
             // Modify w and h to fit
-            let w = if x >= WORLD_SIZE { 0 } else { WORLD_SIZE - x };
-            let h = if y >= WORLD_SIZE { 0 } else { WORLD_SIZE - y };
+            w = w.min(WORLD_SIZE.saturating_sub(x));
+            h = h.min(WORLD_SIZE.saturating_sub(y));
             if w == 0 || h == 0 {
                 return;
             }
@@ -1203,7 +3118,10 @@ impl GameMaster {
             for dx in 0..w {
                 let wx = x + dx;
                 let wy = y + dy;
-                if self.rng.i32(0..128) < chance as i32 || self.powerup_cur == PowerUp::SuperDrill {
+                if self.rng.i32(0..128) < chance as i32 || self.powerup_on(PowerUp::SuperDrill) {
+                    if wy < DIRT_START as usize && self.world_get(wx, wy) == Some(true) {
+                        self.day_sky_drilled = true;
+                    }
                     self.world_set(wx, wy, false);
                     self.stats.drilled += 1;
                     sfx = true;
@@ -1223,9 +3141,12 @@ impl GameMaster {
             || origin_a.y >= origin_b.y + size_b.y)
     }
 
+    // Uses the miner's hitbox, which can be smaller than the sprite
     fn collides_player(&self, pos: &Pos, size: &Pos) -> bool {
-        let player_size = Pos::new(PLAYER_SIZE as i16, PLAYER_SIZE as i16);
-        self.collides(&self.player_pos, &player_size, pos, size)
+        let hitbox = self.miner().hitbox;
+        let inset = (PLAYER_SIZE as i16 - hitbox) / 2;
+        let origin = Pos::new(self.player_pos.x + inset, self.player_pos.y + inset);
+        self.collides(&origin, &Pos::new(hitbox, hitbox), pos, size)
     }
 
     fn collides_world(&self, pos: &Pos, size: &Pos) -> bool {
@@ -1285,34 +3206,66 @@ impl GameMaster {
         // Other ents check their own collisions
 
         // Check for gold collection
+        let got = self.day_nuggets_got;
+        let mut i = 0;
         unsafe {
             #[allow(static_mut_refs)]
             // TODO: THIS IS BAD
             self.gold_locs.retain(|gold| {
                 let collided = GM.collides_player(gold, &Pos { x: 4, y: 4 });
+                // Drops are pushed after the world gen gold
+                let nugget = i < GM.day_nuggets;
+                i += 1;
                 if collided {
+                    if nugget {
+                        GM.day_nuggets_got += 1;
+                    }
                     GM.sfx_gold();
                     GM.drill_heat = GM.drill_heat.saturating_sub(GM.drill_heat_max / 10);
-                    GM.gold += 1;
-                    GM.stats.collected += 1;
+                    let mut gold = GM.diff().gold_yield;
+                    if GM.powerup_on(PowerUp::DoubleGold) {
+                        gold *= 2;
+                    }
+                    GM.gold += gold;
+                    GM.stats.collected += gold;
+                    GM.day_gold += gold;
+                    // Only arcade runs count towards unlocks
+                    if GM.game_mode == 0 {
+                        GM.save.gold += gold as u32;
+                    }
                     false
                 } else {
                     true
                 }
             });
+            self.day_nuggets -= self.day_nuggets_got - got;
+        }
+        // Check for coolant pickups
+        for i in (0..self.coolant_locs.len()).rev() {
+            if self.collides_player(&self.coolant_locs[i], &Pos::new(8, 8)) {
+                self.coolant_locs.remove(i);
+                self.drill_heat = self.drill_heat.saturating_sub(self.drill_heat_max / 3);
+                self.sfx_ok();
+            }
         }
         // Check for collisions with doors
         let door_collide = self.collides_player(&self.door_loc, &Pos { x: 8, y: 8 });
-        if door_collide {
+        if door_collide && !self.door_locked() {
             self.door_timer = self.door_timer.saturating_add(1);
             self.sfx_door();
             // Watch for game over, only allow level change if alive
-            if self.door_timer == DOOR_TIMER && self.hp > 0 {
+            let door_time = if self.perk_has(Perk::QuickDoor) {
+                DOOR_TIMER / 2
+            } else {
+                DOOR_TIMER
+            };
+            if self.door_timer == door_time && self.hp > 0 {
                 if self.game_mode == 1 {
                     // Training
                     self.screen_set(Screen::Start);
                     return;
                 }
+                self.achieve_day();
                 self.next_level();
             }
         } else {
@@ -1325,22 +3278,142 @@ impl GameMaster {
                 self.powerup_taken = true;
                 self.stats.powerups += 1;
                 self.sfx_ok();
-                // Random powerup
-                let pu_index = self.rng.u32(0..POWERUP_TYPES.len() as u32) as usize;
-                self.powerup_cur = POWERUP_TYPES[pu_index].clone();
-                // 10 seconds at 60fps
-                self.powerup_frames = POWERUP_FRAMES;
-                // Give 1 HP if invincible
-                if self.powerup_cur == PowerUp::Invincible {
-                    self.hp += 1;
-                    if self.hp > MAX_HP {
-                        self.hp = MAX_HP;
-                    }
+                // Bank it if there's room, otherwise it goes off right away
+                let pu = self.powerup_roll();
+                if self.held.len() < self.held_slots as usize {
+                    self.held.push(pu);
+                    trace(format!("HELD: {}", pu.name()));
+                } else {
+                    self.powerup_start(pu);
                 }
             }
         }
     }
 
+    // Weighted pick, rarer powerups get more common on later days
+    fn powerup_roll(&mut self) -> PowerUp {
+        let total: u32 = POWERUP_TYPES.iter().map(|p| p.weight(self.lvl)).sum();
+        let mut roll = self.rng.u32(0..total.max(1));
+        for pu in POWERUP_TYPES {
+            let w = pu.weight(self.lvl);
+            if roll < w {
+                return pu;
+            }
+            roll -= w;
+        }
+        PowerUp::SuperDrill
+    }
+
+    // Powerups stack, picking one up again tops up its timer
+    fn powerup_start(&mut self, pu: PowerUp) {
+        let frames = self.diff().powerup_frames as u32 * pu.duration() / 100;
+        self.powerup_frames[pu as usize] = frames as u16;
+        trace(format!("POWERUP: {}", pu.name()));
+        // Give a heart if invincible
+        if pu == PowerUp::Invincible {
+            self.hp = (self.hp + 2).min(self.max_hp());
+        }
+    }
+
+    fn powerup_use(&mut self) {
+        if self.held.is_empty() {
+            self.sfx_deny();
+            return;
+        }
+        let pu = self.held.remove(0);
+        self.powerup_start(pu);
+        self.sfx_ok();
+    }
+
+    fn powerup_on(&self, pu: PowerUp) -> bool {
+        self.powerup_frames[pu as usize] > 0
+    }
+
+    // Door won't open while the boss is still around
+    fn door_locked(&self) -> bool {
+        self.boss.is_some() || !self.objective_met()
+    }
+
+    fn objective_met(&self) -> bool {
+        match self.cur_lvl_data.objective {
+            Objective::Door => true,
+            Objective::Gold(n) => self.day_gold >= n,
+            Objective::Survive(t) => self.day_frames >= t as u32 * 60,
+            Objective::Depth(y) => self.day_depth >= y,
+            Objective::Bombers => self.enemy_locs(Enemy::Bomber).is_empty(),
+            // The canary trails a few steps behind, so measure from the player on the door
+            Objective::Canary => match &self.canary {
+                Some(c) => {
+                    self.collides_player(&self.door_loc, &Pos::new(8, 8))
+                        && c.pos().distance(&self.player_pos) <= CANARY_LAG as f32 * 1.5
+                }
+                None => false,
+            },
+        }
+    }
+
+    // Progress towards the day's objective, empty for plain door days
+    fn objective_str(&self) -> String {
+        match self.cur_lvl_data.objective {
+            Objective::Door => String::new(),
+            Objective::Gold(n) => format!("GOLD {}/{}", self.day_gold.min(n), n),
+            Objective::Survive(t) => {
+                let left = (t as u32).saturating_sub(self.day_frames / 60);
+                if left > 0 {
+                    format!("SURVIVE {}", left)
+                } else {
+                    "SURVIVED".to_string()
+                }
+            }
+            Objective::Depth(y) => format!("DEPTH {}/{}", self.day_depth.min(y), y),
+            Objective::Bombers => format!("BOMBERS {}", self.enemy_locs(Enemy::Bomber).len()),
+            Objective::Canary => match &self.canary {
+                Some(c) => format!("CANARY {}/{}", c.hp, CANARY_HP),
+                None => String::new(),
+            },
+        }
+    }
+
+    fn up_objective(&mut self) {
+        self.day_frames += 1;
+        self.day_depth = self.day_depth.max(self.player_pos.y);
+        let Some(canary) = &mut self.canary else {
+            return;
+        };
+        // Step along the player's path whenever the player moves
+        if self.player_pos != canary.trail[0] {
+            canary.trail.insert(0, self.player_pos);
+            canary.trail.pop();
+        }
+        canary.hit_frames = canary.hit_frames.saturating_sub(1);
+        if canary.hit_frames > 0 {
+            return;
+        }
+        let pos = canary.pos();
+        let hit = self
+            .rain_locs
+            .iter()
+            .position(|rain| self.collides(&pos, &Pos::new(8, 6), rain, &Pos::new(2, 2)));
+        let Some(i) = hit else {
+            return;
+        };
+        self.rain_locs.remove(i);
+        let canary = self.canary.as_mut().unwrap();
+        canary.hp -= 1;
+        canary.hit_frames = HIT_FRAMES * 2;
+        let dead = canary.hp == 0;
+        self.sfx_hit();
+        if dead {
+            // A new one flies in from the surface
+            self.fx_locs.push(pos);
+            self.fx_times.push(FX_FRAMES);
+            self.sfx_kill();
+            let start = Pos::new(self.player_pos.x, DIRT_START as i16 - 8);
+            self.canary = Some(Canary::new(start));
+            trace("CANARY LOST");
+        }
+    }
+
     fn player_wrap(&mut self) {
         if self.player_pos.x < 0 {
             self.player_pos.x = (WORLD_SIZE - PLAYER_SIZE as usize) as i16;
@@ -1352,16 +3425,39 @@ impl GameMaster {
         }
     }
 
-    fn player_dmg(&mut self, from: &str) {
-        if self.powerup_cur == PowerUp::Invincible {
+    fn player_dmg(&mut self, src: DmgSrc) {
+        if self.powerup_on(PowerUp::Invincible) || self.inv_frames > 0 {
+            return;
+        }
+        let mut dmg = src.amount();
+        if self.armoured(src.kind()) {
+            dmg -= 1;
+            // Half a heart still gets through half the time
+            if dmg == 0 && self.rng.bool() {
+                dmg = 1;
+            }
+        }
+        if dmg == 0 {
+            self.sfx_hit();
             return;
         }
         self.dmg_frames = DMG_FRAMES;
-        self.hp = self.hp.saturating_sub(1);
-        self.stats.dmg += 1;
+        self.inv_frames = INV_FRAMES;
+        self.hp = self.hp.saturating_sub(dmg);
+        self.stats.dmg += dmg as u16;
+        self.day_hurt = true;
         self.sfx_dmg();
-        trace(format!("DMG FROM: {}: HP={}", from, self.hp));
-        self.last_dmg_from = from.to_string();
+        trace(format!("DMG FROM: {}: {} HP={}", src.name(), dmg, self.hp));
+        self.last_dmg_from = src.name().to_string();
+    }
+
+    fn armoured(&self, kind: DmgType) -> bool {
+        if kind == DmgType::Acid && self.passive_has(Passive::AcidSkin) {
+            return true;
+        }
+        ARMOUR[..self.armour as usize]
+            .iter()
+            .any(|(_, k)| *k == kind)
     }
 
     fn render_gold_text(&mut self, x: i32, y: i32, amt: u16) {
@@ -1414,14 +3510,48 @@ impl GameMaster {
         tone(f | (1000 << 16), 4, 100, TONE_TRIANGLE);
     }
 
+    fn sfx_vent(&mut self) {
+        tone(1200 | (300 << 16), 30, 80, TONE_NOISE);
+    }
+
+    fn sfx_rumble(&mut self) {
+        let f = self.rng.u32(40..70);
+        tone(f | ((f / 2) << 16), 10, 100, TONE_NOISE);
+    }
+
     fn sfx_explode(&mut self) {
         tone(200 | (500 << 16), 60, 128, TONE_NOISE);
     }
 
+    fn sfx_hit(&mut self) {
+        tone(300 | (150 << 16), 6, 100, TONE_PULSE2);
+    }
+
+    fn sfx_kill(&mut self) {
+        tone(400 | (100 << 16), 16, 100, TONE_NOISE);
+    }
+
+    fn sfx_shoot(&mut self) {
+        tone(700 | (300 << 16), 8, 70, TONE_PULSE2);
+    }
+
+    fn sfx_alert(&mut self) {
+        tone(600 | (900 << 16), 6, 80, TONE_PULSE2);
+    }
+
+    fn sfx_beam(&mut self) {
+        let f = self.rng.u32(90..110);
+        tone(f | (140 << 16), 8, 40, TONE_TRIANGLE);
+    }
+
     fn sfx_ok(&mut self) {
         tone(400 | (600 << 16), 4, 128, TONE_PULSE1);
     }
 
+    fn sfx_achieve(&mut self) {
+        tone(600 | (1200 << 16), 24, 100, TONE_PULSE2);
+    }
+
     fn sfx_deny(&mut self) {
         tone(400, 2, 128, TONE_PULSE1);
     }
@@ -1431,28 +3561,129 @@ impl GameMaster {
     }
 
     fn next_level(&mut self) {
+        self.save.store();
         if self.game_mode != 1 {
             self.lvl += 1;
             // Check if we just completed the last level
             if self.lvl > MAX_LVL - 1 {
+                if self.difficulty == MAX_DIFF - 1 {
+                    self.achieve(Achievement::Hell);
+                }
+                if self.stats.overheats == 0 {
+                    self.achieve(Achievement::IceCold);
+                }
                 self.screen_set(Screen::GameOver);
                 return;
             }
         }
         self.world_reset();
+        self.day_setup();
+        self.shop_roll();
+        // Draft a perk before hitting the shop
+        self.perk_roll();
+        if !self.perk_offer.is_empty() {
+            self.screen_set(Screen::Draft);
+        } else {
+            self.screen_set(self.shop_screen());
+        }
+        return;
+    }
+
+    // Settings and world for the current day
+    fn day_setup(&mut self) {
         self.cur_lvl_data = LVLS[self.lvl];
-        self.cur_lvl_data.apply_difficulty(self.difficulty);
+        self.cur_lvl_data.apply_difficulty(self.diff());
+        if self.game_mode == 3 {
+            self.cur_lvl_data.apply_sandbox();
+        }
+        if self.mutator_on(Mutator::DoubleRain) {
+            let lvl = &mut self.cur_lvl_data;
+            lvl.rain_chance_rte = (lvl.rain_chance_rte / 2).max(1);
+            lvl.rain_amount_rte = (lvl.rain_amount_rte / 2).max(1);
+        }
+        self.lvl_base = self.cur_lvl_data;
+        if self.director_on {
+            // Carry the tension over from the last day
+            self.cur_lvl_data
+                .apply_tension(&self.lvl_base, self.director.tension);
+            self.director.dmg_mark = self.stats.dmg;
+            self.director.gold_mark = self.stats.collected;
+            self.director.overheats = 0;
+        }
         self.world_gen();
-        self.screen_set(Screen::Shop);
-        return;
+    }
+
+    // Start the same day over after a death
+    fn day_restart(&mut self, lost: u16) {
+        self.gold -= lost;
+        self.world_reset();
+        self.day_setup();
+        self.hp = self.start_hp();
+        self.respawn_frames = RESPAWN_FRAMES;
+        self.respawn_gold = lost;
+        self.screen_set(Screen::Game);
+        trace(format!("RESPAWN: lives={} lost={}", self.lives, lost));
+    }
+
+    fn continue_price(&self) -> u16 {
+        self.diff()
+            .price(CONTINUE_COST * (self.continues as u16 + 1))
+    }
+
+    // Review how the day is going and nudge the tension
+    fn up_director(&mut self) {
+        // Frame 0 is the start of the day, nothing to review yet
+        if !self.director_on || self.frame == 0 || self.frame % DIRECTOR_INTERVAL != 0 {
+            return;
+        }
+        let d = self.director;
+        let dmg = self.stats.dmg - d.dmg_mark;
+        let gold = self.stats.collected - d.gold_mark;
+        let struggling = self.hp <= 4 || dmg >= 2 || d.overheats >= 2;
+        let cruising = self.hp >= 10 && dmg == 0 && gold >= 3 && d.overheats == 0;
+        let tension = if struggling {
+            (d.tension - 1).max(DIRECTOR_MIN)
+        } else if cruising {
+            (d.tension + 1).min(DIRECTOR_MAX)
+        } else {
+            d.tension
+        };
+        trace(format!(
+            "DIRECTOR: hp={} dmg={} gold={} heat={} tension {} -> {}",
+            self.hp, dmg, gold, d.overheats, d.tension, tension
+        ));
+        if tension != d.tension {
+            self.cur_lvl_data.apply_tension(&self.lvl_base, tension);
+            trace(format!(
+                "DIRECTOR: drone_rte={} drone_limit={} rain={}/{}",
+                self.cur_lvl_data.drone_rte,
+                self.cur_lvl_data.drone_limit,
+                self.cur_lvl_data.rain_chance_rte,
+                self.cur_lvl_data.rain_amount_rte
+            ));
+        }
+        self.director = Director {
+            tension,
+            dmg_mark: self.stats.dmg,
+            gold_mark: self.stats.collected,
+            overheats: 0,
+        };
     }
 
     fn up_drill(&mut self) {
-        if self.is_drilling {
-            self.drill_heat = self.drill_heat.saturating_add(1);
+        self.vent_cooldown = self.vent_cooldown.saturating_sub(1);
+        if self.is_drilling && !self.powerup_on(PowerUp::HeatSink) {
+            let heat = if self.passive_has(Passive::HotDrill) {
+                2
+            } else {
+                1
+            };
+            self.drill_heat = self.drill_heat.saturating_add(heat);
         } else if self.drill_overheat {
             // Slower cooldown when overheated
             self.drill_heat = self.drill_heat.saturating_sub(1);
+        } else if self.perk_has(Perk::CoolHead) {
+            self.drill_heat = self.drill_heat.saturating_sub(3);
         } else {
             self.drill_heat = self.drill_heat.saturating_sub(2);
         }
@@ -1461,9 +3692,19 @@ impl GameMaster {
                 self.sfx_drill_warn();
             }
         }
-        if self.drill_heat >= self.drill_heat_max && self.powerup_cur != PowerUp::SuperDrill {
+        if self.drill_heat >= self.drill_heat_max
+            && !self.powerup_on(PowerUp::SuperDrill)
+            && !self.drill_overheat
+        {
             self.drill_overheat = true;
+            self.director.overheats = self.director.overheats.saturating_add(1);
+            self.stats.overheats += 1;
             self.sfx_drill_overheat();
+            // Harder difficulties punish overheating
+            self.stun_frames = self.diff().overheat_stun;
+            if self.diff().overheat_dmg {
+                self.player_dmg(DmgSrc::Heat);
+            }
         }
         // Release overheat when cooled down
         if self.drill_heat == 0 {
@@ -1522,7 +3763,12 @@ impl GameMaster {
         }
         for &i in hits_player.iter().rev() {
             self.rain_locs.remove(i);
-            self.player_dmg("rain");
+            if self.perk_has(Perk::RainCoat) && !self.rain_coat_used {
+                self.rain_coat_used = true;
+                self.sfx_hit();
+            } else if !self.powerup_on(PowerUp::Umbrella) {
+                self.player_dmg(DmgSrc::Rain);
+            }
         }
         // Clear world blocks
         for i in (0..self.rain_locs.len()).rev() {
@@ -1554,6 +3800,8 @@ impl GameMaster {
         }
         for &i in hits_gold.iter().rev() {
             if i < self.rain_locs.len() {
+                let mut g = 0;
+                let mut lost = 0;
                 self.gold_locs.retain(|gold| {
                     let wx = self.rain_locs[i].x as usize;
                     let wy = self.rain_locs[i].y as usize;
@@ -1561,8 +3809,13 @@ impl GameMaster {
                         && gold.x < (wx + 4) as i16
                         && gold.y >= wy as i16
                         && gold.y < (wy + 4) as i16;
+                    if hit && g < self.day_nuggets {
+                        lost += 1;
+                    }
+                    g += 1;
                     !hit
                 });
+                self.day_nuggets -= lost;
                 // Remove a chunk of world where gold was
                 self.world_set_circle(
                     self.rain_locs[i].x as usize,
@@ -1576,31 +3829,30 @@ impl GameMaster {
     }
 
     fn up_drones(&mut self) {
-        // Add drones
-        if self.frame % self.cur_lvl_data.drone_rte as u32 == 0
-            && self.drone_locs.len() < self.cur_lvl_data.drone_limit
-        {
-            let x = self.rng.i16(0..(WORLD_SIZE as i16));
-            self.drone_locs.push(Pos::new(x, 0));
-        }
-        // Check for collision with player
-        let mut hits_player: Vec<usize> = Vec::new();
-        for (i, drone) in self.drone_locs.iter().enumerate() {
-            let collides = self.collides_player(drone, &Pos::new(8, 4));
-            if collides {
-                hits_player.push(i);
+        if !self.fast_pass {
+            // Add drones
+            if self.frame % self.cur_lvl_data.drone_rte as u32 == 0
+                && self.drone_locs.len() < self.cur_lvl_data.drone_limit
+            {
+                let x = self.rng.i16(0..(WORLD_SIZE as i16));
+                self.enemy_spawn(Enemy::Drone, Pos::new(x, 0));
             }
-        }
-        for &i in hits_player.iter().rev() {
-            self.drone_locs.remove(i);
-            self.player_dmg("drone");
+            self.enemy_contact(Enemy::Drone);
         }
         // Update drones every N frames
         if self.frame % 16 != 0 {
             return;
         }
         // Move towards player
-        for drone in &mut self.drone_locs {
+        // Use open tunnels when there are any, otherwise burrow straight in
+        for i in 0..self.drone_locs.len() {
+            let path = self.path_step(&self.drone_locs[i]);
+            let drone = &mut self.drone_locs[i];
+            if let Some(step) = path {
+                drone.x += step.x;
+                drone.y += step.y;
+                continue;
+            }
             let dx = self.player_pos.x - drone.x;
             let dy = self.player_pos.y - drone.y;
             let dist = self.player_pos.distance(drone);
@@ -1620,17 +3872,8 @@ impl GameMaster {
     }
 
     fn up_flies(&mut self) {
-        // Check for collision with player
-        let mut hits_player: Vec<usize> = Vec::new();
-        for (i, fly) in self.fly_locs.iter().enumerate() {
-            let collides = self.collides_player(fly, &Pos::new(8, 4));
-            if collides {
-                hits_player.push(i);
-            }
-        }
-        for &i in hits_player.iter().rev() {
-            self.fly_locs.remove(i);
-            self.player_dmg("fly");
+        if !self.fast_pass {
+            self.enemy_contact(Enemy::Fly);
         }
         // Only move every N frames
         if self.frame % 8 != 0 {
@@ -1683,24 +3926,28 @@ impl GameMaster {
     }
 
     fn up_sliders(&mut self) {
-        // Check for collision with player
-        let mut hits_player: Vec<usize> = Vec::new();
-        for (i, slider) in self.slider_locs.iter().enumerate() {
-            let collides = self.collides_player(slider, &Pos::new(8, 4));
-            if collides {
-                hits_player.push(i);
+        if !self.fast_pass {
+            self.enemy_contact(Enemy::Slider);
+        }
+        let size = Enemy::Slider.size();
+        // Sliders drop down shafts when the floor is gone
+        if self.frame % 2 == 0 {
+            for i in 0..self.slider_locs.len() {
+                if !self.enemy_grounded(&self.slider_locs[i], &size) {
+                    self.slider_locs[i].y += 1;
+                }
             }
         }
-        for &i in hits_player.iter().rev() {
-            self.slider_locs.remove(i);
-            self.player_dmg("slider");
-        }
         // Only move every N frames
         if self.frame % 8 != 0 {
             return;
         }
         // Sliders move left and right only
-        for slider in &mut self.slider_locs {
+        for i in 0..self.slider_locs.len() {
+            if !self.enemy_grounded(&self.slider_locs[i], &size) {
+                continue;
+            }
+            let slider = &mut self.slider_locs[i];
             let dir = self.rng.i32(0..2);
             match dir {
                 0 => {
@@ -1725,45 +3972,165 @@ impl GameMaster {
         }
     }
 
-    // Seekers move towards the player when the player gets close
+    // Seekers move towards the player once they have seen them
     fn up_seekers(&mut self) {
-        // Check for collision with player
-        let mut hits_player: Vec<usize> = Vec::new();
-        for (i, seeker) in self.seeker_locs.iter().enumerate() {
-            let collides = self.collides_player(seeker, &Pos::new(8, 8));
-            if collides {
-                hits_player.push(i);
-            }
-        }
-        for &i in hits_player.iter().rev() {
-            self.seeker_locs.remove(i);
-            self.player_dmg("seeker");
+        if !self.fast_pass {
+            self.up_seeker_sight();
         }
         // Only move every N frames
         if self.frame % 16 != 0 {
             return;
         }
-        // Move towards player if close enough
-        for seeker in &mut self.seeker_locs {
-            let dx = self.player_pos.x - seeker.x;
-            let dy = self.player_pos.y - seeker.y;
-            let dist = self.player_pos.distance(seeker);
-            if dist < 64. && dist > 1. {
-                let step_x = (dx as f32 / dist).round() as i16;
-                let step_y = (dy as f32 / dist).round() as i16;
-                seeker.x += step_x;
-                seeker.y += step_y;
-                seeker.clamp_to_world();
+        // Chase the player
+        // Seekers don't dig, they only follow open tunnels
+        for i in 0..self.seeker_locs.len() {
+            let seeker = self.seeker_locs[i];
+            let dist = self.player_pos.distance(&seeker);
+            if self.seeker_alert[i] > 0 && dist > 1. {
+                if let Some(step) = self.path_step(&seeker) {
+                    self.seeker_locs[i].x += step.x;
+                    self.seeker_locs[i].y += step.y;
+                }
             }
         }
-        // Check for collision with world
+    }
+
+    // Contact, and spotting the player
+    fn up_seeker_sight(&mut self) {
+        self.enemy_contact(Enemy::Seeker);
+        // Look for the player
+        let eye = Pos::new(self.player_pos.x + 4, self.player_pos.y + 4);
+        let hidden = self.perk_has(Perk::Stillness) && self.dir == 0 && !self.is_drilling;
         for i in 0..self.seeker_locs.len() {
-            let seeker = &self.seeker_locs[i];
-            self.world_set_area(seeker.x as usize, seeker.y as usize, 8, 8, false);
+            let seeker = self.seeker_locs[i];
+            let center = Pos::new(seeker.x + 4, seeker.y + 4);
+            if !hidden && center.distance(&eye) < SEEKER_SIGHT && self.world_los(&center, &eye) {
+                if self.seeker_alert[i] == 0 {
+                    self.sfx_alert();
+                }
+                self.seeker_alert[i] = SEEKER_MEMORY;
+            } else {
+                self.seeker_alert[i] = self.seeker_alert[i].saturating_sub(1);
+            }
         }
     }
 
+    // Breadth first search out from the player over a coarse grid of the world
+    // Each node ends up holding the number of steps to the player through open space
+    // Nodes are the top left cell of an 8x8 area so enemies fit through
+    fn up_paths(&mut self) {
+        if self.frame % PATH_INTERVAL != 0 {
+            return;
+        }
+        let cell = PATH_CELL as i16;
+        self.path_field.clear();
+        for cy in 0..PATH_GRID as i16 {
+            for cx in 0..PATH_GRID as i16 {
+                let open =
+                    !self.collides_world(&Pos::new(cx * cell, cy * cell), &Pos::new(cell, cell));
+                self.path_field
+                    .push(if open { PATH_UNSEEN } else { PATH_BLOCKED });
+            }
+        }
+        let (px, py) = self.path_node(&self.player_pos);
+        let start = py * PATH_GRID + px;
+        self.path_field[start] = 0;
+        self.path_queue.clear();
+        self.path_queue.push(start as u16);
+        let mut head = 0;
+        while head < self.path_queue.len() {
+            let node = self.path_queue[head] as usize;
+            head += 1;
+            let d = self.path_field[node];
+            let (cx, cy) = (node % PATH_GRID, node / PATH_GRID);
+            for (ox, oy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+                let nx = cx as i32 + ox;
+                let ny = cy as i32 + oy;
+                if nx < 0 || ny < 0 || nx >= PATH_GRID as i32 - 1 || ny >= PATH_GRID as i32 - 1 {
+                    continue;
+                }
+                let (nx, ny) = (nx as usize, ny as usize);
+                let ni = ny * PATH_GRID + nx;
+                if self.path_field[ni] == PATH_UNSEEN && self.path_node_open(nx, ny) {
+                    self.path_field[ni] = (d + 1).min(PATH_UNSEEN - 1);
+                    self.path_queue.push(ni as u16);
+                }
+            }
+        }
+    }
+
+    // Closest node to a position
+    fn path_node(&self, pos: &Pos) -> (usize, usize) {
+        let max = PATH_GRID as i16 - 2;
+        let cell = PATH_CELL as i16;
+        let cx = ((pos.x + cell / 2) / cell).clamp(0, max);
+        let cy = ((pos.y + cell / 2) / cell).clamp(0, max);
+        (cx as usize, cy as usize)
+    }
+
+    fn path_node_open(&self, cx: usize, cy: usize) -> bool {
+        let i = cy * PATH_GRID + cx;
+        self.path_field[i] != PATH_BLOCKED
+            && self.path_field[i + 1] != PATH_BLOCKED
+            && self.path_field[i + PATH_GRID] != PATH_BLOCKED
+            && self.path_field[i + PATH_GRID + 1] != PATH_BLOCKED
+    }
+
+    // One pixel step towards the player through open space
+    // None when there is no open path from here or next door
+    fn path_step(&self, pos: &Pos) -> Option<Pos> {
+        if self.path_field.is_empty() {
+            return None;
+        }
+        let (cx, cy) = self.path_node(pos);
+        let mut best = self.path_field[cy * PATH_GRID + cx];
+        let mut target = self.player_pos;
+        for (ox, oy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+            let nx = cx as i32 + ox;
+            let ny = cy as i32 + oy;
+            if nx < 0 || ny < 0 || nx >= PATH_GRID as i32 || ny >= PATH_GRID as i32 {
+                continue;
+            }
+            let nd = self.path_field[ny as usize * PATH_GRID + nx as usize];
+            if nd < best {
+                best = nd;
+                target = Pos::new(
+                    (nx * PATH_CELL as i32) as i16,
+                    (ny * PATH_CELL as i32) as i16,
+                );
+            }
+        }
+        if best >= PATH_UNSEEN {
+            return None;
+        }
+        Some(Pos::new(
+            (target.x - pos.x).signum(),
+            (target.y - pos.y).signum(),
+        ))
+    }
+
     fn up_bombers(&mut self) {
+        if !self.fast_pass {
+            self.up_bomber_fuses();
+        }
+        // Bombers fall down
+        if self.frame % 8 != 0 {
+            return;
+        }
+        for i in 0..self.bomber_locs.len() {
+            let bomber = self.bomber_locs[i].clone();
+            let collides = self.collides_world(&Pos::new(bomber.x, bomber.y + 8), &Pos::new(8, 1));
+            if !collides {
+                self.bomber_locs[i].y += 1;
+                self.bomber_locs[i].clamp_to_world();
+            }
+        }
+    }
+
+    // Contact, fuses and explosions
+    fn up_bomber_fuses(&mut self) {
+        // Bombers don't hurt on touch but can be drilled
+        self.enemy_contact(Enemy::Bomber);
         // Check for collision with player
         let mut hits: Vec<usize> = Vec::new();
         let hit_dist = 24.;
@@ -1773,58 +4140,731 @@ impl GameMaster {
                 hits.push(i);
             }
         }
-        for &i in hits.iter().rev() {
-            if self.bomber_times[i] == 0 {
-                self.bomber_times[i] = 64; // Start countdown
+        for &i in hits.iter().rev() {
+            if self.bomber_times[i] == 0 {
+                self.bomber_times[i] = 64; // Start countdown
+            }
+        }
+        for time in &mut self.bomber_times {
+            if *time > 0 {
+                *time = time.saturating_sub(1);
+            }
+        }
+        // Bombers explode when timer reaches 1 (0 is safe state)
+        let mut to_explode: Vec<usize> = Vec::new();
+        for (i, &time) in self.bomber_times.iter().enumerate() {
+            if time == 1 {
+                to_explode.push(i);
+            }
+        }
+        // TODO: There may be a bug here
+        // Bombers indexes may shift when removing bombers
+        // Should use a bombers_alive Vec<bool> instead
+        for &i in to_explode.iter().rev() {
+            let bomber = self.bomber_locs[i].clone();
+            // Clear area around bomber
+            self.world_set_circle(bomber.x as usize, bomber.y as usize, 16, false);
+            let bomb_offset = Pos::new(bomber.x - 16, bomber.y - 16);
+            let hit_player = self.collides_player(&bomb_offset, &Pos::new(32, 32));
+            if hit_player {
+                self.player_dmg(DmgSrc::Enemy(Enemy::Bomber));
+            }
+            self.enemy_remove(Enemy::Bomber, i);
+            self.sfx_explode();
+            self.drop_gold(&bomber, 2, 5);
+            self.blast_enemies(&bomber, 20., 2);
+            // Set off any player bombs caught in the blast
+            self.chain_blast(&bomber, 16.);
+        }
+    }
+
+    // UFOs hover over the surface and beam things back up to it
+    fn up_ufos(&mut self) {
+        if !self.fast_pass {
+            self.up_ufo_beams();
+        }
+        // Drift towards the player when not beaming
+        if self.frame % 4 != 0 {
+            return;
+        }
+        for i in 0..self.ufo_locs.len() {
+            if self.ufo_times[i] < UFO_BEAM_FRAMES {
+                continue;
+            }
+            let ufo = &mut self.ufo_locs[i];
+            if ufo.x < self.player_pos.x {
+                ufo.x += 1;
+            } else if ufo.x > self.player_pos.x {
+                ufo.x -= 1;
+            }
+            ufo.clamp_to_world();
+        }
+    }
+
+    // Spawning, contact and the beam cycle
+    fn up_ufo_beams(&mut self) {
+        // Add ufos
+        if self.frame % self.cur_lvl_data.ufo_rte as u32 == 0
+            && self.ufo_locs.len() < self.cur_lvl_data.ufo_limit
+        {
+            let x = if self.rng.bool() {
+                0
+            } else {
+                WORLD_SIZE as i16 - 8
+            };
+            let y = self.rng.i16(4..(DIRT_START as i16 - 12));
+            self.enemy_spawn(Enemy::Ufo, Pos::new(x, y));
+        }
+        self.enemy_contact(Enemy::Ufo);
+        for time in &mut self.ufo_times {
+            *time = if *time == 0 { UFO_CYCLE } else { *time - 1 };
+        }
+        for i in 0..self.ufo_locs.len() {
+            if self.ufo_times[i] < UFO_BEAM_FRAMES {
+                self.ufo_beam(i);
+            }
+        }
+    }
+
+    // Beams stop at the first solid cell under the ufo
+    fn ufo_beam_end(&self, ufo: &Pos) -> i16 {
+        let mut end = WORLD_SIZE as i16;
+        for x in ufo.x + 2..(ufo.x + 6).min(WORLD_SIZE as i16) {
+            for y in ufo.y + 8..end {
+                if self.world_get(x as usize, y as usize) == Some(true) {
+                    end = y;
+                    break;
+                }
+            }
+        }
+        end
+    }
+
+    fn ufo_beam(&mut self, i: usize) {
+        let ufo = self.ufo_locs[i];
+        let top = ufo.y + 8;
+        let end = self.ufo_beam_end(&ufo);
+        if self.frame % 8 == 0 {
+            self.sfx_beam();
+        }
+        // Loose dirt at the end of the beam gets pulled up, solid dirt is cover
+        if self.frame % 4 == 0 {
+            for x in ufo.x + 2..(ufo.x + 6).min(WORLD_SIZE as i16) {
+                let (wx, wy) = (x as usize, end as usize);
+                if self.world_get(wx, wy) == Some(true) && self.world_neighbors(wx, wy) < 4 {
+                    self.world_set(wx, wy, false);
+                    if end > DIRT_START as i16 {
+                        self.world_set(wx, wy - 1, true);
+                    }
+                }
+            }
+        }
+        let beam_pos = Pos::new(ufo.x + 2, top);
+        let beam_size = Pos::new(4, end - top);
+        // Lift the player back to the surface
+        let surface = DIRT_START as i16 - PLAYER_SIZE as i16;
+        if self.player_pos.y > surface && self.collides_player(&beam_pos, &beam_size) {
+            let cache = self.player_pos;
+            self.player_pos.y -= 2;
+            self.player_collide_world(cache);
+        }
+        // Lift gold
+        for g in 0..self.gold_locs.len() {
+            let gold = self.gold_locs[g];
+            if gold.y > DIRT_START as i16 - 4
+                && self.collides(&gold, &Pos::new(4, 4), &beam_pos, &beam_size)
+            {
+                self.gold_locs[g].y -= 1;
+            }
+        }
+    }
+
+    // Turrets sit in the dirt and spit acid at the player when they can see them
+    fn up_turrets(&mut self) {
+        self.enemy_contact(Enemy::Turret);
+        let eye = Pos::new(self.player_pos.x + 4, self.player_pos.y + 4);
+        for i in 0..self.turret_locs.len() {
+            self.turret_times[i] = self.turret_times[i].saturating_sub(1);
+            if self.turret_times[i] > 0 {
+                continue;
+            }
+            let turret = self.turret_locs[i];
+            let center = Pos::new(turret.x + 4, turret.y + 4);
+            let dist = center.distance(&eye);
+            if dist < TURRET_RANGE && dist > 1. && self.world_los(&center, &eye) {
+                self.turret_times[i] = TURRET_RATE;
+                let speed = 12.;
+                let vx = ((eye.x - center.x) as f32 / dist * speed) as i8;
+                let vy = ((eye.y - center.y) as f32 / dist * speed) as i8;
+                self.projectile_spawn(center, vx, vy, 120, 2, Shooter::Enemy(Enemy::Turret));
+                self.sfx_shoot();
+            }
+        }
+    }
+
+    // Final day boss, a big mining machine that chews through the dirt after the player
+    fn up_boss(&mut self) {
+        let Some(mut boss) = self.boss else {
+            return;
+        };
+        if boss.hp == 0 {
+            self.boss_defeat(&boss);
+            return;
+        }
+        let size = Pos::new(BOSS_SIZE, BOSS_SIZE);
+        let phase = boss.phase();
+        boss.hit_frames = boss.hit_frames.saturating_sub(1);
+        boss.timer = boss.timer.saturating_sub(1);
+        if self.drill_hits(&boss.pos, &size) {
+            if boss.hit_frames == 0 {
+                boss.hp = boss.hp.saturating_sub(self.drill_dmg() as u16);
+                boss.hit_frames = HIT_FRAMES;
+                self.sfx_hit();
+            }
+        } else if self.collides_player(&boss.pos, &size) {
+            self.player_dmg(DmgSrc::Boss);
+        }
+        // Chase the player, faster each phase
+        if self.frame % (4 - phase as u32) == 0 {
+            let center = Pos::new(boss.pos.x + BOSS_SIZE / 2, boss.pos.y + BOSS_SIZE / 2);
+            let target = Pos::new(self.player_pos.x + 4, self.player_pos.y + 4);
+            boss.pos.x += (target.x - center.x).signum();
+            boss.pos.y += (target.y - center.y).signum();
+            boss.pos.x = boss.pos.x.clamp(0, WORLD_SIZE as i16 - BOSS_SIZE);
+            boss.pos.y = boss
+                .pos
+                .y
+                .clamp(DIRT_START as i16 - BOSS_SIZE, WORLD_SIZE as i16 - BOSS_SIZE);
+            // Carve through anything in the way
+            self.world_set_area(boss.pos.x as usize, boss.pos.y as usize, 16, 16, false);
+        }
+        // Attacks
+        if boss.timer == 0 {
+            let center = Pos::new(boss.pos.x + BOSS_SIZE / 2, boss.pos.y + BOSS_SIZE / 2);
+            match phase {
+                0 => {
+                    // Grind out a cavern around itself
+                    self.world_set_circle(center.x as usize, center.y as usize, 20, false);
+                    self.sfx_explode();
+                    boss.timer = 240;
+                }
+                1 => {
+                    // Spray acid in every direction
+                    self.boss_spray(&center);
+                    boss.timer = 150;
+                }
+                _ => {
+                    // Spray and bring the ceiling down over the player
+                    self.boss_spray(&center);
+                    self.boss_collapse();
+                    boss.timer = 120;
+                }
+            }
+        }
+        self.boss = Some(boss);
+    }
+
+    fn boss_spray(&mut self, center: &Pos) {
+        for (vx, vy) in [
+            (12, 0),
+            (8, 8),
+            (0, 12),
+            (-8, 8),
+            (-12, 0),
+            (-8, -8),
+            (0, -12),
+            (8, -8),
+        ] {
+            self.projectile_spawn(*center, vx, vy, 90, 3, Shooter::Boss);
+        }
+        self.sfx_shoot();
+    }
+
+    // Loose dirt above the player that will crumble down on them
+    fn boss_collapse(&mut self) {
+        let x = (self.player_pos.x - 12).max(0);
+        let y = (self.player_pos.y - 16).max(DIRT_START as i16);
+        // Stop at the right edge so the dirt doesn't wrap onto the next rows
+        for dx in 0..32.min(WORLD_SIZE as i16 - x) {
+            if self.rng.i32(0..3) > 0 {
+                self.world_set_area((x + dx) as usize, y as usize, 1, 3, true);
+            }
+        }
+        self.sfx_explode();
+    }
+
+    fn boss_hit(&mut self, dmg: u16) {
+        if let Some(boss) = &mut self.boss {
+            boss.hp = boss.hp.saturating_sub(dmg);
+            boss.hit_frames = HIT_FRAMES;
+        }
+    }
+
+    fn boss_defeat(&mut self, boss: &Boss) {
+        self.boss = None;
+        for i in 0..4 {
+            self.fx_locs
+                .push(Pos::new(boss.pos.x + (i % 2) * 8, boss.pos.y + (i / 2) * 8));
+            self.fx_times.push(FX_FRAMES);
+        }
+        self.world_set_circle(
+            (boss.pos.x + BOSS_SIZE / 2) as usize,
+            (boss.pos.y + BOSS_SIZE / 2) as usize,
+            24,
+            false,
+        );
+        self.drop_gold(&boss.pos, 12, 16);
+        self.sfx_explode();
+        self.achieve(Achievement::Slayer);
+        trace("BOSS DEFEATED");
+    }
+
+    // Listening for the drill, and the body getting drilled or touched
+    fn up_worm_hits(&mut self) {
+        if self.is_drilling {
+            self.vibration = self.player_pos;
+            self.vibration_frames = 120;
+        }
+        self.vibration_frames = self.vibration_frames.saturating_sub(1);
+        self.enemy_contact(Enemy::Worm);
+        // Body segments can be drilled and hurt on touch
+        let size = Enemy::Worm.size();
+        let mut hits_drill: Vec<usize> = Vec::new();
+        let mut hits_player = false;
+        for (i, body) in self.worm_bodies.iter().enumerate() {
+            for seg in body.segments() {
+                if self.drill_hits(seg, &size) {
+                    hits_drill.push(i);
+                    break;
+                }
+                if self.collides_player(seg, &size) {
+                    hits_player = true;
+                }
+            }
+        }
+        if hits_player {
+            self.player_dmg(DmgSrc::Enemy(Enemy::Worm));
+        }
+        for &i in hits_drill.iter().rev() {
+            self.enemy_hit(Enemy::Worm, i, self.drill_dmg());
+        }
+    }
+
+    // Worms tunnel through the dirt and home in on the sound of drilling
+    fn up_worms(&mut self) {
+        if !self.fast_pass {
+            self.up_worm_hits();
+        }
+        // Only move every N frames
+        if self.frame % 4 != 0 {
+            return;
+        }
+        let size = Enemy::Worm.size();
+        for i in 0..self.worm_locs.len() {
+            let head = self.worm_locs[i];
+            let mut dir = self.worm_bodies[i].dir;
+            if self.vibration_frames > 0 && head.distance(&self.vibration) < WORM_HEARING {
+                dir = Pos::new(
+                    (self.vibration.x - head.x).signum(),
+                    (self.vibration.y - head.y).signum(),
+                );
+            } else if self.rng.i32(0..8) == 0 {
+                dir = Pos::new(self.rng.i16(-1..2), self.rng.i16(-1..2));
+            }
+            let mut next = Pos::new(head.x + dir.x, head.y + dir.y);
+            // Stay underground
+            if next.y < DIRT_START as i16 {
+                next.y = DIRT_START as i16;
+                dir.y = 1;
+            }
+            next.x = next.x.clamp(0, WORLD_SIZE as i16 - size.x);
+            next.y = next.y.min(WORLD_SIZE as i16 - size.y);
+            if next == head {
+                continue;
+            }
+            let body = &mut self.worm_bodies[i];
+            body.dir = dir;
+            body.trail.pop();
+            body.trail.insert(0, head);
+            self.worm_locs[i] = next;
+            // Leave a real tunnel behind
+            self.world_set_area(next.x as usize, next.y as usize, 6, 6, false);
+        }
+    }
+
+    fn projectile_spawn(&mut self, pos: Pos, vx: i8, vy: i8, life: u8, dig: u8, owner: Shooter) {
+        let p = Projectile {
+            x: pos.x * PROJ_SUB,
+            y: pos.y * PROJ_SUB,
+            vx,
+            vy,
+            life,
+            dig,
+            owner,
+        };
+        if let Some(slot) = self.projectiles.iter_mut().find(|p| p.life == 0) {
+            *slot = p;
+        } else if self.projectiles.len() < PROJ_MAX {
+            self.projectiles.push(p);
+        }
+    }
+
+    fn up_projectiles(&mut self) {
+        for i in 0..self.projectiles.len() {
+            let mut p = self.projectiles[i];
+            if p.life == 0 {
+                continue;
+            }
+            p.life -= 1;
+            p.x += p.vx as i16;
+            p.y += p.vy as i16;
+            let pos = p.pos();
+            let size = Pos::new(2, 2);
+            if pos.x < 0 || pos.y < 0 || pos.x >= WORLD_SIZE as i16 || pos.y >= WORLD_SIZE as i16 {
+                p.life = 0;
+            } else if self.collides_world(&pos, &size) {
+                // Hit the dirt
+                if p.dig > 0 {
+                    self.world_set_circle(pos.x as usize, pos.y as usize, p.dig as usize, false);
+                }
+                p.life = 0;
+            } else if self.collides_player(&pos, &size) {
+                self.player_dmg(DmgSrc::Shot(p.owner));
+                p.life = 0;
+            }
+            self.projectiles[i] = p;
+        }
+    }
+
+    fn enemy_locs(&self, kind: Enemy) -> &Vec<Pos> {
+        match kind {
+            Enemy::Drone => &self.drone_locs,
+            Enemy::Fly => &self.fly_locs,
+            Enemy::Slider => &self.slider_locs,
+            Enemy::Seeker => &self.seeker_locs,
+            Enemy::Bomber => &self.bomber_locs,
+            Enemy::Ufo => &self.ufo_locs,
+            Enemy::Turret => &self.turret_locs,
+            Enemy::Worm => &self.worm_locs,
+        }
+    }
+
+    fn enemy_locs_mut(&mut self, kind: Enemy) -> &mut Vec<Pos> {
+        match kind {
+            Enemy::Drone => &mut self.drone_locs,
+            Enemy::Fly => &mut self.fly_locs,
+            Enemy::Slider => &mut self.slider_locs,
+            Enemy::Seeker => &mut self.seeker_locs,
+            Enemy::Bomber => &mut self.bomber_locs,
+            Enemy::Ufo => &mut self.ufo_locs,
+            Enemy::Turret => &mut self.turret_locs,
+            Enemy::Worm => &mut self.worm_locs,
+        }
+    }
+
+    fn enemy_health(&self, kind: Enemy) -> &Vec<Health> {
+        match kind {
+            Enemy::Drone => &self.drone_hp,
+            Enemy::Fly => &self.fly_hp,
+            Enemy::Slider => &self.slider_hp,
+            Enemy::Seeker => &self.seeker_hp,
+            Enemy::Bomber => &self.bomber_hp,
+            Enemy::Ufo => &self.ufo_hp,
+            Enemy::Turret => &self.turret_hp,
+            Enemy::Worm => &self.worm_hp,
+        }
+    }
+
+    fn enemy_health_mut(&mut self, kind: Enemy) -> &mut Vec<Health> {
+        match kind {
+            Enemy::Drone => &mut self.drone_hp,
+            Enemy::Fly => &mut self.fly_hp,
+            Enemy::Slider => &mut self.slider_hp,
+            Enemy::Seeker => &mut self.seeker_hp,
+            Enemy::Bomber => &mut self.bomber_hp,
+            Enemy::Ufo => &mut self.ufo_hp,
+            Enemy::Turret => &mut self.turret_hp,
+            Enemy::Worm => &mut self.worm_hp,
+        }
+    }
+
+    // Always add and remove enemies through these so the side lists stay in sync
+    fn enemy_spawn(&mut self, kind: Enemy, pos: Pos) {
+        self.enemy_locs_mut(kind).push(pos);
+        self.enemy_health_mut(kind).push(Health::new(kind.max_hp()));
+        if kind == Enemy::Bomber {
+            self.bomber_times.push(0);
+        }
+        if kind == Enemy::Ufo {
+            self.ufo_times.push(UFO_CYCLE);
+        }
+        if kind == Enemy::Seeker {
+            self.seeker_alert.push(0);
+        }
+        if kind == Enemy::Turret {
+            self.turret_times.push(TURRET_RATE);
+        }
+        if kind == Enemy::Worm {
+            self.worm_bodies.push(WormBody::new(pos));
+        }
+    }
+
+    fn enemy_remove(&mut self, kind: Enemy, i: usize) -> Pos {
+        self.enemy_health_mut(kind).remove(i);
+        if kind == Enemy::Bomber {
+            self.bomber_times.remove(i);
+        }
+        if kind == Enemy::Ufo {
+            self.ufo_times.remove(i);
+        }
+        if kind == Enemy::Seeker {
+            self.seeker_alert.remove(i);
+        }
+        if kind == Enemy::Turret {
+            self.turret_times.remove(i);
+        }
+        if kind == Enemy::Worm {
+            self.worm_bodies.remove(i);
+        }
+        self.enemy_locs_mut(kind).remove(i)
+    }
+
+    fn drill_hits(&self, pos: &Pos, size: &Pos) -> bool {
+        self.drill_boxes
+            .iter()
+            .any(|(origin, area)| self.collides(origin, area, pos, size))
+    }
+
+    // Drill damages enemies in front of it
+    // Walking into an enemy without drilling it hurts the player
+    fn enemy_contact(&mut self, kind: Enemy) {
+        let size = kind.size();
+        // Index and whether the drill got it, handled high to low in one pass
+        // so kills and removals never shift an index still to come
+        let mut hits: Vec<(usize, bool)> = Vec::new();
+        for (i, e) in self.enemy_locs(kind).iter().enumerate() {
+            if self.drill_hits(e, &size) {
+                hits.push((i, true));
+            } else if kind != Enemy::Bomber && self.collides_player(e, &size) {
+                hits.push((i, false));
+            }
+        }
+        for &(i, drilled) in hits.iter().rev() {
+            if drilled {
+                self.enemy_hit(kind, i, self.drill_dmg());
+            } else {
+                self.enemy_remove(kind, i);
+                self.player_dmg(DmgSrc::Enemy(kind));
+            }
+        }
+    }
+
+    fn enemy_hit(&mut self, kind: Enemy, i: usize, dmg: u8) {
+        let health = &mut self.enemy_health_mut(kind)[i];
+        if health.hit_frames > 0 {
+            return;
+        }
+        health.hp = health.hp.saturating_sub(dmg);
+        health.hit_frames = HIT_FRAMES;
+        if health.hp == 0 {
+            self.enemy_kill(kind, i);
+        } else {
+            self.sfx_hit();
+        }
+    }
+
+    fn enemy_kill(&mut self, kind: Enemy, i: usize) {
+        if kind == Enemy::Bomber {
+            // Popped bombers go off almost right away, only count the first pop
+            if self.bomber_times[i] == 0 || self.bomber_times[i] > 9 {
+                self.bomber_times[i] = 9;
+                self.day_bombers += 1;
+                if self.day_bombers >= 5 {
+                    self.achieve(Achievement::BomberPop);
+                }
+            }
+            return;
+        }
+        if kind == Enemy::Worm {
+            for seg in self.worm_bodies[i].segments() {
+                self.fx_locs.push(*seg);
+                self.fx_times.push(FX_FRAMES);
+            }
+        }
+        let pos = self.enemy_remove(kind, i);
+        self.fx_locs.push(pos);
+        self.fx_times.push(FX_FRAMES);
+        self.sfx_kill();
+        // Loot
+        if self.rng.i32(0..4) == 0 {
+            self.coolant_locs.push(pos);
+        } else if self.perk_has(Perk::Scavenger) {
+            self.drop_gold(&pos, 2, 5);
+        } else {
+            self.drop_gold(&pos, 1, 3);
+        }
+    }
+
+    fn up_enemy_health(&mut self) {
+        for kind in ENEMY_TYPES {
+            for health in self.enemy_health_mut(kind) {
+                health.hit_frames = health.hit_frames.saturating_sub(1);
+            }
+        }
+        // Death effects
+        for time in &mut self.fx_times {
+            *time = time.saturating_sub(1);
+        }
+        for i in (0..self.fx_times.len()).rev() {
+            if self.fx_times[i] == 0 {
+                self.fx_locs.remove(i);
+                self.fx_times.remove(i);
+            }
+        }
+    }
+
+    // Damage every enemy with its center inside a blast
+    fn blast_enemies(&mut self, center: &Pos, r: f32, dmg: u8) {
+        for kind in ENEMY_TYPES {
+            let size = kind.size();
+            for i in (0..self.enemy_locs(kind).len()).rev() {
+                let e = self.enemy_locs(kind)[i];
+                let mid = Pos::new(e.x + size.x / 2, e.y + size.y / 2);
+                if mid.distance(center) < r {
+                    // Blasts ignore hit flashes
+                    self.enemy_health_mut(kind)[i].hit_frames = 0;
+                    self.enemy_hit(kind, i, dmg);
+                }
+            }
+        }
+        if let Some(boss) = self.boss {
+            let mid = Pos::new(boss.pos.x + BOSS_SIZE / 2, boss.pos.y + BOSS_SIZE / 2);
+            if mid.distance(center) < r + BOSS_SIZE as f32 / 2. {
+                // Bombs kill anything else outright, the boss only takes a chunk
+                self.boss_hit((dmg as u16 * 2).min(BOSS_BLAST_MAX));
+            }
+        }
+    }
+
+    fn enemy_grounded(&self, pos: &Pos, size: &Pos) -> bool {
+        pos.y + size.y >= WORLD_SIZE as i16
+            || self.collides_world(&Pos::new(pos.x, pos.y + size.y), &Pos::new(size.x, 1))
+    }
+
+    // Enemies can't overlap, push them apart along the shallower axis
+    fn up_enemy_collisions(&mut self) {
+        if self.frame % 4 != 0 {
+            return;
+        }
+        let mut ents: Vec<(Enemy, usize, Pos)> = Vec::new();
+        for kind in ENEMY_TYPES {
+            for (i, e) in self.enemy_locs(kind).iter().enumerate() {
+                ents.push((kind, i, *e));
+            }
+        }
+        for a in 0..ents.len() {
+            for b in a + 1..ents.len() {
+                let (kind_a, _, pos_a) = ents[a];
+                let (kind_b, _, pos_b) = ents[b];
+                let (size_a, size_b) = (kind_a.size(), kind_b.size());
+                if !self.collides(&pos_a, &size_a, &pos_b, &size_b) {
+                    continue;
+                }
+                let dx = (pos_b.x * 2 + size_b.x) - (pos_a.x * 2 + size_a.x);
+                let dy = (pos_b.y * 2 + size_b.y) - (pos_a.y * 2 + size_a.y);
+                if dx.abs() >= dy.abs() {
+                    let step = if dx < 0 { -1 } else { 1 };
+                    ents[a].2.x -= step;
+                    ents[b].2.x += step;
+                } else {
+                    let step = if dy < 0 { -1 } else { 1 };
+                    ents[a].2.y -= step;
+                    ents[b].2.y += step;
+                }
+            }
+        }
+        for (kind, i, mut pos) in ents {
+            pos.clamp_to_world();
+            self.enemy_locs_mut(kind)[i] = pos;
+        }
+    }
+
+    // Scatter gold around a position, same spread as a bomber blast
+    fn drop_gold(&mut self, pos: &Pos, min: i16, max: i16) {
+        let drops = self.rng.i16(min..max);
+        for _ in 0..drops {
+            let mut gold = Pos::new(pos.x + self.rng.i16(-16..17), pos.y + self.rng.i16(-16..17));
+            gold.clamp_to_world();
+            self.gold_locs.push(gold);
+        }
+    }
+
+    // Light the fuse of any bombers or player bombs near a blast
+    fn chain_blast(&mut self, center: &Pos, r: f32) {
+        for i in 0..self.bomber_locs.len() {
+            if self.bomber_times[i] == 0 && self.bomber_locs[i].distance(center) < r {
+                self.bomber_times[i] = 9;
+            }
+        }
+        for i in 0..self.bomb_locs.len() {
+            if self.bomb_locs[i].distance(center) < r {
+                self.bomb_times[i] = self.bomb_times[i].min(8);
             }
         }
-        for time in &mut self.bomber_times {
-            if *time > 0 {
-                *time = time.saturating_sub(1);
-            }
+    }
+
+    fn bomb_drop(&mut self) {
+        if self.bombs == 0 {
+            self.sfx_deny();
+            return;
         }
-        // Bombers explode when timer reaches 1 (0 is safe state)
-        let mut to_explode: Vec<usize> = Vec::new();
-        for (i, &time) in self.bomber_times.iter().enumerate() {
-            if time == 1 {
-                to_explode.push(i);
-            }
+        self.bombs -= 1;
+        self.bomb_locs.push(self.player_pos);
+        self.bomb_times.push(BOMB_FUSE);
+        self.sfx_ok();
+    }
+
+    fn bomb_explode(&mut self, bomb: Pos) {
+        let center = Pos::new(bomb.x + 4, bomb.y + 4);
+        let radius = if self.perk_has(Perk::Demolition) {
+            BOMB_RADIUS + 4
+        } else {
+            BOMB_RADIUS
+        };
+        self.world_set_circle(center.x as usize, center.y as usize, radius, false);
+        let r = radius as i16;
+        let blast_offset = Pos::new(center.x - r, center.y - r);
+        if self.collides_player(&blast_offset, &Pos::new(r * 2, r * 2)) {
+            self.player_dmg(DmgSrc::Bomb);
+        }
+        // Player bombs kill anything caught in the blast
+        let r = radius as f32 + 4.;
+        self.blast_enemies(&center, r, u8::MAX);
+        self.sfx_explode();
+        self.chain_blast(&center, r);
+    }
+
+    fn up_bombs(&mut self) {
+        for time in &mut self.bomb_times {
+            *time = time.saturating_sub(1);
         }
-        // TODO: There may be a bug here
-        // Bombers indexes may shift when removing bombers
-        // Should use a bombers_alive Vec<bool> instead
-        for &i in to_explode.iter().rev() {
-            let bomber = self.bomber_locs[i].clone();
-            // Clear area around bomber
-            self.world_set_circle(bomber.x as usize, bomber.y as usize, 16, false);
-            let bomb_offset = Pos::new(bomber.x - 16, bomber.y - 16);
-            let hit_player = self.collides_player(&bomb_offset, &Pos::new(32, 32));
-            if hit_player {
-                self.player_dmg("bomber");
-            }
-            self.bomber_locs.remove(i);
-            self.bomber_times.remove(i);
-            self.sfx_explode();
-            let drops = self.rng.i16(2..5);
-            for _ in 0..drops {
-                let pos = Pos::new(
-                    bomber.x + self.rng.i16(-16..17),
-                    bomber.y + self.rng.i16(-16..17),
-                );
-                self.gold_locs.push(pos);
+        for i in (0..self.bomb_locs.len()).rev() {
+            if self.bomb_times[i] == 0 {
+                let bomb = self.bomb_locs.remove(i);
+                self.bomb_times.remove(i);
+                self.bomb_explode(bomb);
             }
         }
-
-        // Bombers fall down
-        if self.frame % 8 != 0 {
+        // Bombs fall down
+        if self.frame % 4 != 0 {
             return;
         }
-        for i in 0..self.bomber_locs.len() {
-            let bomber = self.bomber_locs[i].clone();
-            let collides = self.collides_world(&Pos::new(bomber.x, bomber.y + 8), &Pos::new(8, 1));
+        for i in 0..self.bomb_locs.len() {
+            let bomb = self.bomb_locs[i];
+            let collides = self.collides_world(&Pos::new(bomb.x, bomb.y + 8), &Pos::new(8, 1));
             if !collides {
-                self.bomber_locs[i].y += 1;
-                self.bomber_locs[i].clamp_to_world();
+                self.bomb_locs[i].y += 1;
+                self.bomb_locs[i].clamp_to_world();
             }
         }
     }
@@ -1843,6 +4883,7 @@ impl GameMaster {
         let start_y = world_offset * world_split;
         let end_y = start_y + world_split;
         let mut to_fall = Vec::new();
+        let mut crushed = 0;
         // NOTE: It's technically possible for to_fall to just hold the index insead of a position
         // This would cut the to_fall size in half
         // But for clarity we will keep it as positions for now
@@ -1851,21 +4892,7 @@ impl GameMaster {
                 if let Some(cell) = self.world_get(x, y) {
                     // Only check alive cells
                     if cell {
-                        let mut neighbors = 0;
-                        for oy in -1..=1 {
-                            for ox in -1..=1 {
-                                if ox == 0 && oy == 0 {
-                                    continue;
-                                }
-                                if let Some(ncell) = self
-                                    .world_get((x as i32 + ox) as usize, (y as i32 + oy) as usize)
-                                {
-                                    if ncell {
-                                        neighbors += 1;
-                                    }
-                                }
-                            }
-                        }
+                        let neighbors = self.world_neighbors(x, y);
                         if neighbors < 4 && to_fall.len() < falling_limit {
                             to_fall.push((x, y));
                         }
@@ -1873,6 +4900,15 @@ impl GameMaster {
                 }
             }
         }
+        // Enemies that dirt might land on
+        let mut landed: Vec<(Enemy, usize, Pos, u8)> = Vec::new();
+        if !to_fall.is_empty() {
+            for kind in ENEMY_TYPES {
+                for (i, e) in self.enemy_locs(kind).iter().enumerate() {
+                    landed.push((kind, i, *e, 0));
+                }
+            }
+        }
         for (x, y) in to_fall {
             // Check for collision below
             if let Some(below) = self.world_get(x, y + 1) {
@@ -1891,26 +4927,127 @@ impl GameMaster {
                     if !collide_with_player {
                         self.world_set(x, y, false);
                         self.world_set(x, y + 1, true);
+                        if let Some(e) = landed.iter_mut().find(|e| {
+                            self.collides(
+                                &Pos::new(x as i16, y as i16 + 1),
+                                &Pos::new(1, 1),
+                                &e.2,
+                                &e.0.size(),
+                            )
+                        }) {
+                            e.3 += 1;
+                        }
+                    } else {
+                        crushed += 1;
                     }
                 }
             }
         }
+        if !self.diff().crush {
+            return;
+        }
+        // Enough dirt on the player hurts
+        if crushed > 0 {
+            self.crush_mass += crushed;
+            if self.crush_mass >= CRUSH_MASS {
+                self.crush_mass = 0;
+                self.player_dmg(DmgSrc::Dirt);
+            }
+        } else {
+            self.crush_mass = self.crush_mass.saturating_sub(1);
+        }
+        // So does enough dirt on an enemy, backwards so kills don't shift indexes
+        for &(kind, i, _, count) in landed.iter().rev() {
+            if count >= CRUSH_ENEMY {
+                self.enemy_hit(kind, i, 1);
+            }
+        }
+    }
+
+    // Drilling under a big overhang can bring it down
+    fn up_caveins(&mut self) {
+        if self.cavein_timer > 0 {
+            self.cavein_timer -= 1;
+            if self.frame % 10 == 0 {
+                self.sfx_rumble();
+            }
+            if self.cavein_timer == 0 {
+                self.cavein();
+            }
+            return;
+        }
+        if !self.is_drilling || self.frame % 30 != 0 {
+            return;
+        }
+        let (open, mass) = self.overhang();
+        if open >= 20 && mass >= 200 {
+            self.cavein_stress += 1;
+            if self.cavein_stress >= CAVEIN_STRESS {
+                self.cavein_stress = 0;
+                self.cavein_timer = CAVEIN_WARN;
+                trace("CAVE-IN");
+            }
+        } else {
+            self.cavein_stress = self.cavein_stress.saturating_sub(1);
+        }
+    }
+
+    // The area over the player's head, columns open right above them and dirt further up
+    fn overhang(&self) -> (u16, u16) {
+        let mut open = 0;
+        let mut mass = 0;
+        for x in self.player_pos.x - 8..self.player_pos.x + 16 {
+            if x < 0 || x >= WORLD_SIZE as i16 {
+                continue;
+            }
+            if self.world_get(x as usize, (self.player_pos.y - 1).max(0) as usize) == Some(false) {
+                open += 1;
+            }
+            for y in self.player_pos.y - 24..self.player_pos.y - 8 {
+                if y >= DIRT_START as i16 && self.world_get(x as usize, y as usize) == Some(true) {
+                    mass += 1;
+                }
+            }
+        }
+        (open, mass)
+    }
+
+    // Crumble the overhang, loose bits fall on their own
+    fn cavein(&mut self) {
+        for x in self.player_pos.x - 8..self.player_pos.x + 16 {
+            for y in self.player_pos.y - 24..self.player_pos.y - 1 {
+                if x < 0 || x >= WORLD_SIZE as i16 || y < DIRT_START as i16 {
+                    continue;
+                }
+                if self.rng.i32(0..3) > 0 {
+                    self.world_set(x as usize, y as usize, false);
+                }
+            }
+        }
+        self.sfx_explode();
     }
 
     fn up_gold(&mut self) {
         if self.frame % 4 != 0 {
             return;
         }
-        // Magnet powerup effect
+        // Magnet powerup effect, the perk is a weaker always on version
         let mut mag_list = Vec::new();
-        if (self.powerup_cur == PowerUp::Magnet) && (self.powerup_frames > 0) {
+        let mag_range = if self.powerup_on(PowerUp::Magnet) {
+            64.
+        } else if self.perk_has(Perk::Magnetism) || self.passive_has(Passive::MagnetSuit) {
+            24.
+        } else {
+            0.
+        };
+        if mag_range > 0. {
             // Move gold towards player
             for (i, gold) in &mut self.gold_locs.iter_mut().enumerate() {
                 let dx = self.player_pos.x - gold.x;
                 let dy = self.player_pos.y - gold.y;
                 let dist = self.player_pos.distance(gold);
                 // TODO: Gold moving is jank
-                if dist < 64. && dist > 1. {
+                if dist < mag_range && dist > 1. {
                     let step_x = (dx as f32 / dist).round() as i16;
                     let step_y = (dy as f32 / dist).round() as i16;
                     gold.x += step_x;
@@ -1942,13 +5079,33 @@ impl GameMaster {
                 }
             }
         }
+        let fall = if self.mutator_on(Mutator::HeavyGold) {
+            2
+        } else {
+            1
+        };
         for gold in to_fall {
             let g = &mut self.gold_locs[gold];
-            g.y += 1;
+            g.y += fall;
             g.clamp_to_world();
         }
     }
 
+    fn up_coolant(&mut self) {
+        // Coolant falls down
+        if self.frame % 8 != 0 {
+            return;
+        }
+        for i in 0..self.coolant_locs.len() {
+            let c = self.coolant_locs[i];
+            let collides = self.collides_world(&Pos::new(c.x, c.y + 8), &Pos::new(8, 1));
+            if !collides {
+                self.coolant_locs[i].y += 1;
+                self.coolant_locs[i].clamp_to_world();
+            }
+        }
+    }
+
     fn up_powerup(&mut self) {
         // Powerups fall down
         if self.frame % 8 != 0 {
@@ -2398,20 +5555,10 @@ impl GameMaster {
         }
         self.seed += 1; // Increment seed while on start screen
         if self.input_check(BUTTON_2) {
-            self.no_input_frames = NO_INPUT_FRAMES_SH;
-            self.pal_index += 1;
-            if self.pal_index >= PALS.len() {
-                self.pal_index = 0;
-            }
-            self.sfx_ok();
+            self.screen_set(Screen::Unlocks);
         }
         if self.input_check(BUTTON_1) {
-            // Seed random with current frame
-            self.rng = Rng::with_seed(self.seed);
-            trace(format!("set seed: {}", self.seed));
-            self.next_level();
-            // Override next level screen set to transition
-            self.screen_set(Screen::Transition);
+            self.screen_set(Screen::Miners);
         }
         if self.input_check(BUTTON_UP) {
             self.no_input_frames = NO_INPUT_FRAMES_SH;
@@ -2430,40 +5577,218 @@ impl GameMaster {
         }
         if self.input_check(BUTTON_RIGHT) {
             self.no_input_frames = NO_INPUT_FRAMES_SH;
-            self.game_mode += 1;
-            // Wrap
-            if self.game_mode > 2 {
-                self.game_mode = 0;
+            // Wrap, skipping modes still locked
+            loop {
+                self.game_mode += 1;
+                if self.game_mode > 3 {
+                    self.game_mode = 0;
+                }
+                if self.unlocked(Unlock::Mode(self.game_mode)) {
+                    break;
+                }
             }
-            self.invincible = self.game_mode == 2;
+            self.invincible = self.game_mode == 2 || self.game_mode == 3;
+            self.sfx_ok();
+        }
+        if self.input_check(BUTTON_LEFT) {
+            self.no_input_frames = NO_INPUT_FRAMES_SH;
+            self.director_on = !self.director_on;
             self.sfx_ok();
         }
         self.up_rain_pos(50, 60, RAIN_MAX / 2, 5);
     }
 
+    fn up_enemies(&mut self) {
+        if self.powerup_on(PowerUp::Freeze) {
+            // Frozen enemies can still be drilled and still hurt to touch
+            if self.fast_pass {
+                return;
+            }
+            for kind in ENEMY_TYPES {
+                self.enemy_contact(kind);
+            }
+            return;
+        }
+        self.up_drones();
+        self.up_flies();
+        self.up_sliders();
+        self.up_seekers();
+        self.up_bombers();
+        self.up_ufos();
+        self.up_worms();
+        // Nothing below moves, the fast pass leaves it alone
+        if self.fast_pass {
+            return;
+        }
+        self.up_turrets();
+        self.up_projectiles();
+    }
+
+    fn up_sc_mutators(&mut self) {
+        if self.screen != Screen::Mutators {
+            return;
+        }
+        self.seed += 1;
+        // Rows are the mutators, the kit, lives, then go
+        let rows = MUTATOR_TYPES.len() + 3;
+        if self.input_check(BUTTON_UP) && self.mutator_sel > 0 {
+            self.no_input_frames = NO_INPUT_FRAMES_SH;
+            self.mutator_sel -= 1;
+            self.sfx_ok();
+        } else if self.input_check(BUTTON_DOWN) && self.mutator_sel < rows - 1 {
+            self.no_input_frames = NO_INPUT_FRAMES_SH;
+            self.mutator_sel += 1;
+            self.sfx_ok();
+        } else if self.input_check(BUTTON_2) {
+            self.screen_set(Screen::Miners);
+        } else if self.input_check(BUTTON_1) {
+            self.no_input_frames = NO_INPUT_FRAMES_SH;
+            if self.mutator_sel < MUTATOR_TYPES.len() {
+                self.mutators ^= 1 << self.mutator_sel;
+                self.sfx_ok();
+            } else if self.mutator_sel == MUTATOR_TYPES.len() {
+                // Next unlocked kit, standard is always open
+                loop {
+                    self.loadout = (self.loadout + 1) % LOADOUTS.len();
+                    if self.unlocked(Unlock::Loadout(self.loadout)) {
+                        break;
+                    }
+                }
+                self.sfx_ok();
+            } else if self.mutator_sel == MUTATOR_TYPES.len() + 1 {
+                self.lives_on = !self.lives_on;
+                self.sfx_ok();
+            } else {
+                self.run_start();
+            }
+        }
+    }
+
+    fn up_sc_unlocks(&mut self) {
+        if self.screen != Screen::Unlocks {
+            return;
+        }
+        // Pick from the palettes opened so far
+        let open = self.pals_open();
+        if self.input_check(BUTTON_RIGHT) {
+            self.no_input_frames = NO_INPUT_FRAMES_SH;
+            self.pal_index = (self.pal_index + 1) % open;
+            self.sfx_ok();
+        } else if self.input_check(BUTTON_LEFT) {
+            self.no_input_frames = NO_INPUT_FRAMES_SH;
+            self.pal_index = (self.pal_index + open - 1) % open;
+            self.sfx_ok();
+        } else if self.input_check(BUTTON_1) {
+            self.screen_set(Screen::Gallery);
+        } else if self.input_check(BUTTON_2) {
+            self.screen_set(Screen::Start);
+        }
+    }
+
+    fn up_sc_gallery(&mut self) {
+        if self.screen != Screen::Gallery {
+            return;
+        }
+        if self.input_check(BUTTON_1) || self.input_check(BUTTON_2) {
+            self.screen_set(Screen::Unlocks);
+        }
+    }
+
+    fn up_sc_miners(&mut self) {
+        if self.screen != Screen::Miners {
+            return;
+        }
+        self.seed += 1;
+        if self.input_check(BUTTON_RIGHT) {
+            self.no_input_frames = NO_INPUT_FRAMES_SH;
+            self.miner = (self.miner + 1) % MINERS.len();
+            self.sfx_ok();
+        } else if self.input_check(BUTTON_LEFT) {
+            self.no_input_frames = NO_INPUT_FRAMES_SH;
+            self.miner = (self.miner + MINERS.len() - 1) % MINERS.len();
+            self.sfx_ok();
+        } else if self.input_check(BUTTON_1) {
+            self.screen_set(Screen::Mutators);
+        } else if self.input_check(BUTTON_2) {
+            self.screen_set(Screen::Start);
+        }
+    }
+
+    fn up_sc_continue(&mut self) {
+        if self.screen != Screen::Continue {
+            return;
+        }
+        self.continue_frames = self.continue_frames.saturating_sub(1);
+        if self.input_check(BUTTON_1) {
+            self.gold -= self.continue_price();
+            self.stats.spent += self.continue_price();
+            self.continues += 1;
+            self.sfx_ok();
+            self.day_restart(0);
+        } else if self.input_check(BUTTON_2) || self.continue_frames == 0 {
+            self.save.store();
+            self.screen_set(Screen::GameOver);
+        }
+    }
+
+    fn run_start(&mut self) {
+        // Seed random with current frame
+        self.rng = Rng::with_seed(self.seed);
+        trace(format!("set seed: {}", self.seed));
+        let kit = &LOADOUTS[self.loadout];
+        let miner = self.miner();
+        self.hp = self.start_hp();
+        self.bombs = (self.bombs + kit.bombs).min(BOMB_MAX);
+        self.drill_speed = miner.drill_speed + kit.drill_speed;
+        self.drill_heat_max = miner.drill_heat_max;
+        self.lives = if self.lives_on { LIVES_START } else { 0 };
+        self.next_level();
+        // Override next level screen set to transition
+        self.screen_set(Screen::Transition);
+    }
+
     fn up_sc_main(&mut self) {
         if self.screen != Screen::Game {
             return;
         }
+        // The day holds still while the miner beams back in
+        if self.respawn_frames > 0 {
+            self.respawn_frames -= 1;
+            if self.respawn_frames == 0 {
+                self.inv_frames = INV_FRAMES;
+            }
+            return;
+        }
+        self.inv_frames = self.inv_frames.saturating_sub(1);
         self.input_main();
         self.player_collide_misc();
 
         self.up_drill();
+        self.up_caveins();
+        self.up_director();
+        self.up_paths();
 
         self.up_rain_col();
-        self.up_drones();
-        self.up_flies();
-        self.up_sliders();
-        self.up_seekers();
-        self.up_bombers();
+        self.up_enemies();
+        // Fast enemies get an extra update every other frame
+        if self.mutator_on(Mutator::FastEnemies) && self.frame % 2 == 0 {
+            self.fast_pass = true;
+            self.up_enemies();
+            self.fast_pass = false;
+        }
+        self.up_boss();
+        self.up_objective();
+        self.up_enemy_collisions();
+        self.up_bombs();
+        self.up_enemy_health();
+        self.up_coolant();
         self.up_powerup();
         self.up_gold();
         self.up_world();
 
         // Powerup frames countdown
-        self.powerup_frames = self.powerup_frames.saturating_sub(1);
-        if self.powerup_frames == 0 {
-            self.powerup_cur = PowerUp::None;
+        for frames in self.powerup_frames.iter_mut() {
+            *frames = frames.saturating_sub(1);
         }
 
         // Check for game over
@@ -2471,7 +5796,16 @@ impl GameMaster {
             self.gameover_acc += 1;
             if self.gameover_acc > 120 {
                 self.gameover_acc = 120;
-                self.screen_set(Screen::GameOver);
+                if self.lives > 0 {
+                    self.lives -= 1;
+                    self.day_restart((self.gold as u32 * LIFE_GOLD_LOSS as u32 / 100) as u16);
+                } else if self.lives_on && self.gold >= self.continue_price() {
+                    self.continue_frames = CONTINUE_FRAMES;
+                    self.screen_set(Screen::Continue);
+                } else {
+                    self.save.store();
+                    self.screen_set(Screen::GameOver);
+                }
             }
         }
 
@@ -2487,6 +5821,61 @@ impl GameMaster {
         }
     }
 
+    // Offer up to 3 perks the player doesn't have yet
+    fn perk_roll(&mut self) {
+        let mut pool: Vec<Perk> = PERK_TYPES
+            .iter()
+            .copied()
+            .filter(|p| !self.perk_has(*p))
+            .collect();
+        self.perk_offer.clear();
+        self.perk_sel = 0;
+        while self.perk_offer.len() < 3 && !pool.is_empty() {
+            let i = self.rng.usize(0..pool.len());
+            self.perk_offer.push(pool.remove(i));
+        }
+    }
+
+    fn shop_screen(&self) -> Screen {
+        if self.mutator_on(Mutator::NoShop) {
+            Screen::Transition
+        } else {
+            Screen::Shop
+        }
+    }
+
+    fn miner(&self) -> &'static Miner {
+        &MINERS[self.miner]
+    }
+
+    fn passive_has(&self, p: Passive) -> bool {
+        self.miner().passive == Some(p)
+    }
+
+    fn perk_has(&self, perk: Perk) -> bool {
+        self.perks & (1 << perk as u8) != 0
+    }
+
+    fn up_sc_draft(&mut self) {
+        if self.screen != Screen::Draft {
+            return;
+        }
+        if self.input_check(BUTTON_UP) && self.perk_sel > 0 {
+            self.no_input_frames = NO_INPUT_FRAMES_SH;
+            self.perk_sel -= 1;
+            self.sfx_ok();
+        } else if self.input_check(BUTTON_DOWN) && self.perk_sel < self.perk_offer.len() - 1 {
+            self.no_input_frames = NO_INPUT_FRAMES_SH;
+            self.perk_sel += 1;
+            self.sfx_ok();
+        } else if self.input_check(BUTTON_1) {
+            let perk = self.perk_offer[self.perk_sel];
+            self.perks |= 1 << perk as u8;
+            trace(format!("PERK: {}", perk.name()));
+            self.screen_set(self.shop_screen());
+        }
+    }
+
     fn up_sc_shop(&mut self) {
         if self.screen != Screen::Shop {
             return;
@@ -2499,46 +5888,246 @@ impl GameMaster {
             self.purchased = 0;
             self.no_input_frames = NO_INPUT_FRAMES_SH;
         }
-        if self.input_check(BUTTON_UP) {
-            // Buy heart piece
+        // Rows are the stock, then reroll, then leave
+        let rows = self.shop_stock.len() + 2;
+        if self.input_check(BUTTON_UP) && self.shop_sel > 0 {
             self.no_input_frames = NO_INPUT_FRAMES_SH;
-            if self.gold >= self.cost_heart && self.hp < MAX_HP {
-                self.gold = self.gold.saturating_sub(self.cost_heart);
-                self.stats.spent += self.cost_heart;
-                self.hp += 1;
-                self.purchased = 1;
-                self.sfx_ok();
+            self.shop_sel -= 1;
+            self.sfx_ok();
+        } else if self.input_check(BUTTON_DOWN) && self.shop_sel < rows - 1 {
+            self.no_input_frames = NO_INPUT_FRAMES_SH;
+            self.shop_sel += 1;
+            self.sfx_ok();
+        } else if self.input_check(BUTTON_1) {
+            self.no_input_frames = NO_INPUT_FRAMES_SH;
+            if self.shop_sel == rows - 1 {
+                self.screen_set(Screen::Workshop);
+            } else if self.shop_sel == rows - 2 {
+                let cost = self.shop_reroll_price();
+                if self.gold >= cost {
+                    self.gold -= cost;
+                    self.stats.spent += cost;
+                    self.shop_rerolls += 1;
+                    self.shop_roll();
+                    self.shop_sel = self.shop_stock.len();
+                    self.sfx_ok();
+                } else {
+                    bad_purchase(self);
+                }
             } else {
-                bad_purchase(self);
+                let i = self.shop_stock[self.shop_sel];
+                let cost = self.shop_price(i, 0);
+                if self.gold >= cost && self.shop_can_buy(SHOP_CATALOGUE[i].item) {
+                    self.gold -= cost;
+                    self.stats.spent += cost;
+                    self.shop_apply(SHOP_CATALOGUE[i].item, true);
+                    self.shop_bought[i] = self.shop_bought[i].saturating_add(1);
+                    self.shop_msg = "PURCHASED!";
+                    self.purchased = i as u8 + 1;
+                    self.sfx_ok();
+                } else {
+                    bad_purchase(self);
+                }
             }
-        } else if self.input_check(BUTTON_LEFT) {
-            // Buy drill speed
+        } else if self.input_check(BUTTON_2) && self.shop_sel < self.shop_stock.len() {
+            // Sell back for half of what it went for
             self.no_input_frames = NO_INPUT_FRAMES_SH;
-            if self.gold >= self.cost_drill_speed && self.drill_speed < 128 {
-                self.gold = self.gold.saturating_sub(self.cost_drill_speed);
-                self.stats.spent += self.cost_drill_speed;
-                self.drill_speed += 8;
-                self.purchased = 2;
+            let i = self.shop_stock[self.shop_sel];
+            if self.shop_can_sell(i) {
+                self.gold += self.shop_sell_price(i);
+                self.shop_apply(SHOP_CATALOGUE[i].item, false);
+                self.shop_bought[i] -= 1;
+                self.shop_msg = "SOLD!";
+                self.purchased = i as u8 + 1;
                 self.sfx_ok();
             } else {
-                bad_purchase(self);
+                self.sfx_deny();
+            }
+        }
+        self.up_rain_pos(100, 80, RAIN_MAX / 2, 2);
+    }
+
+    // Pick the day's stock from the catalogue
+    fn shop_roll(&mut self) {
+        let no_pu = self.mutator_on(Mutator::NoPowerups);
+        let mut pool: Vec<usize> = (0..SHOP_CATALOGUE.len())
+            .filter(|i| !(no_pu && SHOP_CATALOGUE[*i].item == ShopItem::Powerup))
+            .filter(|i| self.unlocked(Unlock::Shop(SHOP_CATALOGUE[*i].item)))
+            .filter(|i| self.lives_on || SHOP_CATALOGUE[*i].item != ShopItem::Life)
+            .collect();
+        self.shop_stock.clear();
+        while self.shop_stock.len() < SHOP_STOCK && !pool.is_empty() {
+            let i = self.rng.usize(0..pool.len());
+            self.shop_stock.push(pool.remove(i));
+        }
+        self.shop_stock.sort();
+    }
+
+    // Price after `less` fewer purchases, scaled by difficulty and perks
+    fn shop_price(&self, i: usize, less: u8) -> u16 {
+        let entry = &SHOP_CATALOGUE[i];
+        let bought = self.shop_bought[i].saturating_sub(less) as u32;
+        let cost = entry.cost as u32 * (100 + entry.step as u32 * bought) / 100;
+        let mut cost = self.diff().price(cost as u16);
+        if self.perk_has(Perk::Bargain) {
+            cost = (cost * 4 / 5).max(1);
+        }
+        cost
+    }
+
+    fn shop_sell_price(&self, i: usize) -> u16 {
+        self.shop_price(i, 1) / 2
+    }
+
+    fn shop_reroll_price(&self) -> u16 {
+        SHOP_REROLL + self.shop_rerolls as u16 * 2
+    }
+
+    fn shop_can_buy(&self, item: ShopItem) -> bool {
+        match item {
+            ShopItem::Heart => self.hp < self.max_hp(),
+            ShopItem::DrillSpeed => self.drill_speed < 128,
+            ShopItem::DrillCool => self.drill_heat_max < 1024,
+            ShopItem::Bomb => self.bombs < BOMB_MAX,
+            ShopItem::Armour => (self.armour as usize) < ARMOUR.len(),
+            ShopItem::Powerup => self.held.len() < self.held_slots as usize,
+            ShopItem::Coolant => self.coolant_extra < COOLANT_MAX,
+            ShopItem::Life => self.lives < LIVES_MAX,
+        }
+    }
+
+    // Only what was bought this run can go back, and only while it's still held
+    fn shop_can_sell(&self, i: usize) -> bool {
+        if self.shop_bought[i] == 0 {
+            return false;
+        }
+        match SHOP_CATALOGUE[i].item {
+            // Always keep at least one heart
+            ShopItem::Heart => self.hp > 2,
+            ShopItem::DrillSpeed | ShopItem::DrillCool => true,
+            ShopItem::Bomb => self.bombs > 0,
+            ShopItem::Armour => self.armour > 0,
+            ShopItem::Powerup => self.held_bought > 0,
+            ShopItem::Coolant => self.coolant_extra > 0,
+            ShopItem::Life => self.lives > 0,
+        }
+    }
+
+    // Add or take away one of an item
+    fn shop_apply(&mut self, item: ShopItem, add: bool) {
+        match (item, add) {
+            (ShopItem::Heart, true) => self.hp = (self.hp + 2).min(self.max_hp()),
+            (ShopItem::Heart, false) => self.hp -= 2,
+            (ShopItem::DrillSpeed, true) => self.drill_speed += 8,
+            (ShopItem::DrillSpeed, false) => self.drill_speed -= 8,
+            (ShopItem::DrillCool, true) => self.drill_heat_max += 64,
+            (ShopItem::DrillCool, false) => self.drill_heat_max -= 64,
+            (ShopItem::Bomb, true) => self.bombs += 1,
+            (ShopItem::Bomb, false) => self.bombs -= 1,
+            (ShopItem::Armour, true) => self.armour += 1,
+            (ShopItem::Armour, false) => self.armour -= 1,
+            (ShopItem::Powerup, true) => {
+                let pu = self.powerup_roll();
+                self.held.push(pu);
+                self.held_bought += 1;
+            }
+            (ShopItem::Powerup, false) => {
+                // Bought ones are the newest, pickups from the day stay put
+                self.held.pop();
+                self.held_bought -= 1;
             }
-        } else if self.input_check(BUTTON_RIGHT) {
-            // Buy drill cooling
+            (ShopItem::Coolant, true) => self.coolant_extra += 1,
+            (ShopItem::Coolant, false) => self.coolant_extra -= 1,
+            (ShopItem::Life, true) => self.lives += 1,
+            (ShopItem::Life, false) => self.lives -= 1,
+        }
+    }
+
+    // How much of an item the player has
+    fn shop_amount(&self, item: ShopItem) -> String {
+        match item {
+            ShopItem::Heart => self.hearts_str(),
+            ShopItem::DrillSpeed => format!("{}/128", self.drill_speed),
+            ShopItem::DrillCool => format!("{}/1024", self.drill_heat_max),
+            ShopItem::Bomb => format!("{}/{}", self.bombs, BOMB_MAX),
+            ShopItem::Armour => match ARMOUR.get(self.armour as usize) {
+                Some((next, _)) => format!("{}/{} {}", self.armour, ARMOUR.len(), next),
+                None => format!("{}/{}", self.armour, ARMOUR.len()),
+            },
+            ShopItem::Powerup => {
+                let names: Vec<&str> = self.held.iter().map(|p| p.name()).collect();
+                format!(
+                    "{}/{} {}",
+                    self.held.len(),
+                    self.held_slots,
+                    names.join(" ")
+                )
+            }
+            ShopItem::Coolant => format!("{}/{} A DAY", self.coolant_extra, COOLANT_MAX),
+            ShopItem::Life => format!("{}/{}", self.lives, LIVES_MAX),
+        }
+    }
+
+    fn up_sc_workshop(&mut self) {
+        if self.screen != Screen::Workshop {
+            return;
+        }
+        if self.input_check(BUTTON_RIGHT) && self.workshop_sel < DRILL_TREE.len() {
+            self.no_input_frames = NO_INPUT_FRAMES_SH;
+            self.workshop_sel += 1;
+            self.sfx_ok();
+        } else if self.input_check(BUTTON_LEFT) && self.workshop_sel > 0 {
             self.no_input_frames = NO_INPUT_FRAMES_SH;
-            if self.gold >= self.cost_drill_cool && self.drill_heat_max < 1024 {
-                self.gold = self.gold.saturating_sub(self.cost_drill_cool);
-                self.stats.spent += self.cost_drill_cool;
-                self.drill_heat_max += 64;
-                self.purchased = 3;
+            self.workshop_sel -= 1;
+            self.sfx_ok();
+        } else if self.input_check(BUTTON_1) {
+            // Buy the selected upgrade
+            self.no_input_frames = NO_INPUT_FRAMES_SH;
+            let i = self.workshop_sel;
+            let cost = self.workshop_cost(i);
+            if self.workshop_state(i) == 1 && self.gold >= cost {
+                self.gold -= cost;
+                self.stats.spent += cost;
+                if i == DRILL_TREE.len() {
+                    self.held_slots = HELD_MAX;
+                } else {
+                    self.drill_tree |= 1 << i;
+                }
                 self.sfx_ok();
             } else {
-                bad_purchase(self);
+                self.sfx_deny();
             }
         } else if self.input_check(BUTTON_DOWN) {
             self.screen_set(Screen::Transition);
         }
-        self.up_rain_pos(100, 80, RAIN_MAX / 2, 2);
+    }
+
+    // The last workshop item after the drill tree is the pocket upgrade
+    fn workshop_cost(&self, i: usize) -> u16 {
+        match DRILL_TREE.get(i) {
+            Some(up) => self.diff().price(up.cost),
+            None => self.diff().price(COST_POCKET),
+        }
+    }
+
+    // 0 locked, 1 for sale, 2 owned
+    fn workshop_state(&self, i: usize) -> u8 {
+        if i == DRILL_TREE.len() {
+            if self.held_slots >= HELD_MAX {
+                2
+            } else {
+                1
+            }
+        } else if self.drill_tree & (1 << i) != 0 {
+            2
+        } else if DRILL_TREE[i]
+            .needs
+            .is_none_or(|n| self.drill_tree & (1 << n) != 0)
+        {
+            1
+        } else {
+            0
+        }
     }
 
     fn up_sc_gameover(&mut self) {
@@ -2546,7 +6135,10 @@ impl GameMaster {
             return;
         }
         if self.input_check_any() {
+            // The save outlives the run
+            let save = self.save;
             *self = GameMaster::new();
+            self.save = save;
             self.no_input_frames = NO_INPUT_FRAMES;
             return;
         }
@@ -2569,6 +6161,14 @@ impl GameMaster {
         }
     }
 
+    // Enemies flash when hit
+    fn enemy_color(&mut self, kind: Enemy, i: usize) {
+        self.colors_set(4);
+        if self.enemy_health(kind)[i].hit_frames > 0 {
+            self.color_flash(4, 2, 4);
+        }
+    }
+
     // Only works on 8x8 sprites
     fn sprite_frame(&self, fps: u8, frames: Vec<[u8; 8]>) -> [u8; 8] {
         let frame_index = (self.frame / (60 / fps as u32)) % (frames.len() as u32);
@@ -2709,15 +6309,13 @@ impl GameMaster {
         self.render_logo_rain(x, y + 18);
 
         // Options
-        let diff_strs: [&str; MAX_DIFF as usize] = [
-            "BABY", "EASY", "MEDIUM", "HARD", "WILD", "OHNO!", "HECK", "HELL",
-        ];
-        let diff_str = diff_strs[self.difficulty as usize];
+        let diff_str = self.diff().name;
         let drill_str = if self.auto_drill { "AUTO" } else { "MANUAL" };
         let mode_str = match self.game_mode {
             0 => "ARCADE",
             1 => "TRAIN",
             2 => "ZOMBIE",
+            3 => "SANDBOX",
             _ => "ARCADE",
         };
         self.colors_set(3);
@@ -2734,13 +6332,28 @@ impl GameMaster {
         text(b"\x86", 92, 12);
         text(b"\x87", 92, 32);
         text(b"\x85", 92, 52);
+        text(b"\x84", 92, 72);
         self.colors_set(4);
         text(mode_str, 104, 62);
+        self.colors_set(3);
+        text(b" ADAPT", 95, 72);
+        self.colors_set(4);
+        text(if self.director_on { "ON" } else { "OFF" }, 104, 82);
+        self.colors_set(3);
+        text(b" UNLOCK", 95, 92);
+        self.color_flash(2, 3, 64);
+        text(b"\x81", 92, 92);
         //
         self.colors_set(1);
         text("GPLv3        v0.96", 13, 150);
     }
 
+    // Hearts out of the max, with halves
+    fn hearts_str(&self) -> String {
+        let half = if self.hp % 2 == 1 { ".5" } else { "" };
+        format!("{}{}/{}", self.hp / 2, half, self.max_hp() / 2)
+    }
+
     fn render_sc_shop(&mut self) {
         if self.screen != Screen::Shop {
             return;
@@ -2767,57 +6380,55 @@ impl GameMaster {
         self.render_gold_text(49, 14 + sy as i32, self.gold);
         self.colors_set(4);
         self.render_gold_text(50, 15 + sy as i32, self.gold);
-        self.colors_set(3);
-        vline(115, 45, 80);
-        // Up
-        text(b" HEART PIECE", 15, 50);
-        self.render_gold_text(120, 50, self.cost_heart);
-        text(format!("{}/8", self.hp), 24, 60);
-        // Left
-        text(b" DRILL SPEED", 15, 80);
-        self.render_gold_text(120, 80, self.cost_drill_speed);
-        text(format!("{}/128", self.drill_speed), 24, 90);
-        // Right
-        text(b" DRILL COOLR", 15, 110);
-        self.color_flash(2, 3, 64);
-        text(b"\x84", 12, 80);
-        text(b"\x86", 12, 50);
-        text(b"\x85", 12, 110);
-        self.colors_set(3);
-        self.render_gold_text(120, 110, self.cost_drill_cool);
-        text(format!("{}/1024", self.drill_heat_max), 24, 120);
-        // Down
+        vline(115, 38, 12 * (self.shop_stock.len() as u32 + 2));
+        let rows = self.shop_stock.len() + 2;
+        for row in 0..rows {
+            let y = 40 + row as i32 * 12;
+            self.colors_set(3);
+            if row < self.shop_stock.len() {
+                let i = self.shop_stock[row];
+                if !self.shop_can_buy(SHOP_CATALOGUE[i].item) {
+                    self.colors_set(2);
+                }
+                text(SHOP_CATALOGUE[i].name, 15, y);
+                self.render_gold_text(120, y, self.shop_price(i, 0));
+            } else if row == rows - 2 {
+                text(b"REROLL", 15, y);
+                self.render_gold_text(120, y, self.shop_reroll_price());
+            } else {
+                self.colors_set(4);
+                text(b"WORKSHOP", 15, y);
+            }
+            if row == self.shop_sel {
+                self.color_flash(2, 3, 64);
+                text(b"\x85", 5, y);
+            }
+        }
+        // Details for the selected item
+        if let Some(&i) = self.shop_stock.get(self.shop_sel) {
+            self.colors_set(4);
+            text(self.shop_amount(SHOP_CATALOGUE[i].item), 8, 118);
+            if self.shop_can_sell(i) {
+                self.colors_set(3);
+                text(b"\x81SELL", 8, 126);
+                self.render_gold_text(50, 126, self.shop_sell_price(i));
+            }
+        }
         self.colors_set(4);
         hline(0, 135, 160);
-        text(b"\x87NEXT  LEVEL", 33, 145);
+        text(b"\x86\x87 PICK  \x80 BUY", 24, 145);
 
         // Purchased
         if self.purchased > 0 {
+            let item = SHOP_CATALOGUE[self.purchased as usize - 1].item;
             self.colors_set(1);
             rect(0, 45, 160, 120);
             self.colors_set(4);
-            text("PURCHASED!", 42, 60);
-            let mut pur_string = String::new();
-            let mut amt_string = String::new();
-            match self.purchased {
-                1 => {
-                    pur_string = "HEART PIECE".to_string();
-                    amt_string = format!("{}/{}", self.hp, 8);
-                }
-                2 => {
-                    pur_string = "DRILL SPEED".to_string();
-                    amt_string = format!("{}/{}", self.drill_speed, 128);
-                }
-                3 => {
-                    pur_string = "DRILL COOLR".to_string();
-                    amt_string = format!("{}/{}", self.drill_heat_max, 1024);
-                }
-                _ => {}
-            }
+            text(self.shop_msg, 42, 60);
             self.colors_set(3);
-            text(pur_string, 38, 80);
+            text(SHOP_CATALOGUE[self.purchased as usize - 1].name, 38, 80);
             self.colors_set(4);
-            text(amt_string, 38, 90);
+            text(self.shop_amount(item), 38, 90);
             self.colors_set(3);
             for x in 0..160 {
                 let sina = -(self.frame as f32 / 320.).sin() * 2.0;
@@ -2836,6 +6447,296 @@ impl GameMaster {
         }
     }
 
+    fn render_sc_workshop(&mut self) {
+        if self.screen != Screen::Workshop {
+            return;
+        }
+        self.colors_set(1);
+        rect(0, 0, 160, 160);
+        self.colors_set(4);
+        text("WORKSHOP", 48, 6);
+        self.colors_set(3);
+        self.render_gold_text(50, 16, self.gold);
+        for i in 0..=DRILL_TREE.len() {
+            let y = 32 + i as i32 * 12;
+            // Upgrades that need another one are indented under it
+            let (name, x) = match DRILL_TREE.get(i) {
+                Some(up) if up.needs.is_some() => (up.name, 24),
+                Some(up) => (up.name, 12),
+                None => ("POCKET", 12),
+            };
+            match self.workshop_state(i) {
+                0 => self.colors_set(2),
+                1 => self.colors_set(3),
+                _ => self.colors_set(4),
+            }
+            text(name, x, y);
+            if self.workshop_state(i) == 2 {
+                text(b"\x80", 150, y);
+            } else {
+                self.render_gold_text(128, y, self.workshop_cost(i));
+            }
+            if i == self.workshop_sel {
+                self.color_flash(2, 3, 64);
+                text(b"\x85", x - 10, y);
+            }
+        }
+        self.colors_set(3);
+        text(b"\x84\x85 PICK  \x80 BUY", 28, 122);
+        self.colors_set(4);
+        hline(0, 135, 160);
+        text(b"\x87NEXT  LEVEL", 33, 145);
+    }
+
+    fn render_sc_continue(&mut self) {
+        if self.screen != Screen::Continue {
+            return;
+        }
+        self.colors_set(1);
+        rect(0, 0, 160, 160);
+        self.color_flash(3, 4, 32);
+        text("CONTINUE?", 44, 40);
+        self.colors_set(4);
+        let secs = self.continue_frames.div_ceil(60);
+        text(format!("{}", secs), 76, 60);
+        // Time left runs down under the count
+        self.colors_set(2);
+        rect(40, 72, 80, 3);
+        self.colors_set(3);
+        rect(
+            40,
+            72,
+            self.continue_frames as u32 * 80 / CONTINUE_FRAMES as u32,
+            3,
+        );
+        self.colors_set(3);
+        text("RESTART THE DAY", 20, 90);
+        text("FOR", 20, 102);
+        self.render_gold_text(52, 102, self.continue_price());
+        text("BANKED", 20, 114);
+        self.render_gold_text(76, 114, self.gold);
+        text(b"\x80 CONTINUE  \x81 GIVE UP", 4, 148);
+    }
+
+    fn render_sc_miners(&mut self) {
+        if self.screen != Screen::Miners {
+            return;
+        }
+        self.colors_set(1);
+        rect(0, 0, 160, 160);
+        self.colors_set(4);
+        text("PICK A MINER", 32, 6);
+        hline(0, 18, 160);
+        let miner = self.miner();
+        // Walk cycle blown up 4x, 0 bits are drawn
+        let sprite = self.sprite_frame(6, miner.sprites.to_vec());
+        for (y, row) in sprite.iter().enumerate() {
+            for x in 0..8 {
+                if row & (0b10000000 >> x) == 0 {
+                    rect(64 + x * 4, 28 + y as i32 * 4, 4, 4);
+                }
+            }
+        }
+        self.color_flash(2, 3, 64);
+        text(b"\x84", 40, 40);
+        text(b"\x85", 112, 40);
+        self.colors_set(4);
+        let x = 80 - miner.name.len() as i32 * 4;
+        text(miner.name, x, 66);
+        self.colors_set(3);
+        text(format!("HEARTS {}", self.start_hp() / 2), 16, 82);
+        text(format!("DRILL  {}", miner.drill_speed), 16, 92);
+        text(format!("HEAT   {}", miner.drill_heat_max), 16, 102);
+        text(format!("SIZE   {}", miner.hitbox), 16, 112);
+        match miner.passive {
+            Some(p) => {
+                self.colors_set(4);
+                text(p.name(), 8, 124);
+                self.colors_set(3);
+                text(p.desc(), 8, 133);
+            }
+            None => text("NO TRAIT", 8, 124),
+        }
+        text(b"\x80 PICK  \x81 BACK", 12, 148);
+    }
+
+    fn render_sc_mutators(&mut self) {
+        if self.screen != Screen::Mutators {
+            return;
+        }
+        self.colors_set(1);
+        rect(0, 0, 160, 160);
+        self.colors_set(4);
+        text("MUTATORS", 48, 6);
+        hline(0, 18, 160);
+        for (i, m) in MUTATOR_TYPES.iter().enumerate() {
+            let y = 24 + i as i32 * 15;
+            let on = self.mutator_on(*m);
+            self.colors_set(if on { 4 } else { 2 });
+            text(if on { b"\x80" } else { b"-" }, 14, y);
+            text(m.name(), 26, y);
+            text(format!("+{}%", m.bonus()), 112, y);
+            self.colors_set(3);
+            text(m.desc(), 26, y + 8);
+            if i == self.mutator_sel {
+                self.color_flash(2, 3, 64);
+                text(b"\x85", 4, y);
+            }
+        }
+        let y = 24 + MUTATOR_TYPES.len() as i32 * 15;
+        self.colors_set(3);
+        text(format!("KIT: {}", LOADOUTS[self.loadout].name), 26, y);
+        if self.mutator_sel == MUTATOR_TYPES.len() {
+            self.color_flash(2, 3, 64);
+            text(b"\x85", 4, y);
+        }
+        let y = y + 10;
+        self.colors_set(3);
+        let lives = if self.lives_on { "ON" } else { "OFF" };
+        text(format!("LIVES: {}", lives), 26, y);
+        if self.mutator_sel == MUTATOR_TYPES.len() + 1 {
+            self.color_flash(2, 3, 64);
+            text(b"\x85", 4, y);
+        }
+        let y = y + 12;
+        self.colors_set(4);
+        text(format!("GO! SCORE x{}%", self.score_mult()), 26, y);
+        if self.mutator_sel == MUTATOR_TYPES.len() + 2 {
+            self.color_flash(2, 3, 64);
+            text(b"\x85", 4, y);
+        }
+        self.colors_set(3);
+        text(b"\x80 TOGGLE  \x81 BACK", 12, 148);
+    }
+
+    fn render_sc_unlocks(&mut self) {
+        if self.screen != Screen::Unlocks {
+            return;
+        }
+        self.colors_set(1);
+        rect(0, 0, 160, 160);
+        self.colors_set(4);
+        text("UNLOCKS", 52, 6);
+        hline(0, 18, 160);
+        self.colors_set(3);
+        text(format!("LIFETIME GOLD {}", self.save.gold), 8, 24);
+        for (i, e) in UNLOCKS.iter().enumerate() {
+            let y = 38 + i as i32 * 14;
+            let open = self.save.gold >= e.gold;
+            self.colors_set(if open { 4 } else { 2 });
+            text(e.name, 8, y);
+            if open {
+                text("OPEN", 120, y);
+                continue;
+            }
+            // Progress bar towards the goal
+            let bar = 56;
+            let done = self.save.gold * bar / e.gold;
+            self.colors_set(2);
+            rect(100, y + 1, bar, 5);
+            self.colors_set(3);
+            rect(100, y + 1, done, 5);
+        }
+        self.colors_set(3);
+        text(b"\x84\x85", 8, 136);
+        text(
+            format!("PALETTE {}/{}", self.pal_index + 1, self.pals_open()),
+            28,
+            136,
+        );
+        text(b"\x80 GALLERY  \x81 BACK", 8, 148);
+    }
+
+    fn render_sc_gallery(&mut self) {
+        if self.screen != Screen::Gallery {
+            return;
+        }
+        self.colors_set(1);
+        rect(0, 0, 160, 160);
+        self.colors_set(4);
+        text("ACHIEVEMENTS", 32, 6);
+        hline(0, 18, 160);
+        let got = ACHIEVEMENT_TYPES
+            .iter()
+            .filter(|a| self.achieved(**a))
+            .count();
+        self.colors_set(3);
+        text(format!("{}/{}", got, ACHIEVEMENT_TYPES.len()), 8, 22);
+        for (i, a) in ACHIEVEMENT_TYPES.iter().enumerate() {
+            let y = 34 + i as i32 * 16;
+            let got = self.achieved(*a);
+            self.colors_set(if got { 4 } else { 2 });
+            text(if got { b"\x80" } else { b"-" }, 6, y);
+            text(if got { a.name() } else { "???" }, 20, y);
+            self.colors_set(3);
+            text(a.desc(), 20, y + 8);
+        }
+        self.colors_set(3);
+        text(b"\x81 BACK", 8, 148);
+    }
+
+    // Shown over any screen so day end unlocks still get seen
+    fn render_toast(&mut self) {
+        let Some(a) = self.toasts.first() else {
+            return;
+        };
+        let name = a.name();
+        self.colors_set(1);
+        rect(0, 140, 160, 20);
+        self.color_flash(3, 4, 32);
+        hline(0, 140, 160);
+        text("ACHIEVEMENT!", 8, 143);
+        self.colors_set(4);
+        text(name, 8, 151);
+    }
+
+    fn render_sc_draft(&mut self) {
+        if self.screen != Screen::Draft {
+            return;
+        }
+        self.colors_set(1);
+        rect(0, 0, 160, 160);
+        self.colors_set(4);
+        text("PICK A PERK", 36, 6);
+        hline(0, 18, 160);
+        for i in 0..self.perk_offer.len() {
+            let perk = self.perk_offer[i];
+            let y = 28 + i as i32 * 24;
+            self.colors_set(if i == self.perk_sel { 4 } else { 2 });
+            rect(6, y - 3, 148, 21);
+            self.colors_set(if i == self.perk_sel { 1 } else { 3 });
+            text(perk.name(), 10, y);
+            text(perk.desc(), 10, y + 9);
+        }
+        self.colors_set(3);
+        text(b"\x86\x87 PICK  \x80 TAKE", 24, 96);
+        self.render_perks(106);
+    }
+
+    // Run summary, every perk taken so far
+    fn render_perks(&mut self, y: i32) {
+        self.colors_set(4);
+        hline(0, y, 160);
+        text("RUN", 4, y + 4);
+        self.colors_set(3);
+        let mut n = 0;
+        for perk in PERK_TYPES {
+            if !self.perk_has(perk) {
+                continue;
+            }
+            let x = 4 + (n % 2) * 80;
+            let row = y + 14 + (n / 2) * 9;
+            // Names are cut to fit two columns
+            let name = &perk.name()[..perk.name().len().min(9)];
+            text(name, x, row);
+            n += 1;
+        }
+        if n == 0 {
+            self.colors_set(2);
+            text("NO PERKS YET", 4, y + 14);
+        }
+    }
+
     fn render_sc_transition(&mut self) {
         if self.screen != Screen::Transition {
             return;
@@ -2848,6 +6749,8 @@ impl GameMaster {
         hline(62, 30, 60);
         self.colors_set(2);
         text(self.cur_lvl_data.text, 62, 40);
+        self.colors_set(4);
+        text(self.cur_lvl_data.objective.name(), 62, 82);
 
         self.colors_set(3);
         text("WEATHER", 62, 90);
@@ -2911,6 +6814,19 @@ impl GameMaster {
         self.render_gold_text(25, 75, self.stats.spent);
         text("FINAL", 16, 90);
         self.render_gold_text(25, 100, self.gold);
+        // Score and the mutators behind it
+        self.colors_set(4);
+        text("SCORE", 96, 40);
+        let score = self.stats.collected as u32 * self.score_mult() as u32 / 100;
+        text(format!("{}", score), 96, 50);
+        self.colors_set(3);
+        let mut y = 65;
+        for m in MUTATOR_TYPES {
+            if self.mutator_on(m) {
+                text(m.name(), 92, y);
+                y += 9;
+            }
+        }
         let stat_index = (self.frame / 120) % 4;
         let stat_text = match stat_index {
             0 => "DRILLED",
@@ -2931,6 +6847,22 @@ impl GameMaster {
         rect(0, 120, 160, 38);
         self.colors_set(1);
         text(format!("{}\n {}", stat_text, stat_value), 16, 130);
+        // Unlocks this run pushed lifetime gold past
+        let earned = if self.game_mode == 0 {
+            self.stats.collected as u32
+        } else {
+            0
+        };
+        let new: Vec<&str> = UNLOCKS
+            .iter()
+            .filter(|e| self.save.gold - earned < e.gold && self.save.gold >= e.gold)
+            .map(|e| e.name)
+            .collect();
+        if !new.is_empty() {
+            self.color_flash(1, 4, 32);
+            let name = new[(self.frame / 120) as usize % new.len()];
+            text(format!("NEW! {}", name), 16, 147);
+        }
         // for i in 0..160 {
         //     let sina = (self.frame as f32 / 320.).sin() * 2.0;
         //     let sin = ((self.frame as f32 / 16.) + (i as f32 / (4. + sina))).sin();
@@ -2950,6 +6882,18 @@ impl GameMaster {
         }
     }
 
+    // Rattle the whole frame up and down while a cave-in is coming
+    fn render_shake(&mut self) {
+        if self.screen != Screen::Game || self.cavein_timer == 0 {
+            return;
+        }
+        let row = 160 / 4;
+        let dy = self.rng.usize(0..3) * row;
+        let fb = unsafe { &mut *FRAMEBUFFER };
+        let len = fb.len();
+        fb.copy_within(0..len - dy, dy);
+    }
+
     fn render_sc_main(&mut self) {
         if self.screen != Screen::Game {
             return;
@@ -2988,15 +6932,27 @@ impl GameMaster {
         }
 
         // Health
-        for i in 0..self.hp {
+        for i in 0..self.hp.div_ceil(2) {
             self.colors_set(3);
             // rect(45 + i as i32 * 6, 4, 4, 4);
-            blit(&HEART, 76 + i as i32 * 10, 2, 8, 8, BLIT_1BPP);
+            let heart = if i * 2 + 1 == self.hp {
+                HEART_HALF
+            } else {
+                HEART
+            };
+            blit(&heart, 76 + i as i32 * 10, 2, 8, 8, BLIT_1BPP);
         }
         // Gold collected
         // text(self.gold.to_string(), 4, 2);
         self.render_gold_text(4, 2, self.gold);
 
+        // Bombs held
+        if self.bombs > 0 {
+            self.colors_set(4);
+            blit(&BOMB1, 44, 2, 8, 8, BLIT_1BPP);
+            text(format!("{}", self.bombs), 54, 2);
+        }
+
         // Heat bar
         self.colors_set(2);
         let heat_bar_width = 80;
@@ -3008,24 +6964,92 @@ impl GameMaster {
         }
         rect(76, 12, heat_width, 4);
 
-        // Powerups UI
-        if self.powerup_frames > 1 {
-            if self.powerup_frames % 20 < 10 {
+        // Boss health bar
+        if let Some(boss) = self.boss {
+            let boss_bar_width = 80;
+            let boss_width = (boss.hp as u32 * boss_bar_width) / BOSS_HP as u32;
+            self.colors_set(2);
+            rect(76, 18, boss_bar_width, 3);
+            self.colors_set(4);
+            if boss.phase() == 2 {
+                self.color_flash(4, 3, 8);
+            }
+            rect(76, 18, boss_width, 3);
+        }
+
+        // Held powerups, first letter in a box per slot
+        for i in 0..self.held_slots as usize {
+            let x = 54 + i as i32 * 11;
+            self.colors_set(0x20);
+            rect(x, 12, 10, 10);
+            if let Some(pu) = self.held.get(i).copied() {
+                self.colors_set(4);
+                if i == 0 {
+                    self.color_flash(4, 3, 32);
+                }
+                text(&pu.name()[..1], x + 1, 13);
+            }
+        }
+
+        // Lives left, moved down out of the way of the boss bar
+        let lives_y = if self.boss.is_some() { 23 } else { 18 };
+        self.colors_set(3);
+        for i in 0..self.lives as i32 {
+            blit(
+                &self.miner().sprites[0],
+                148 - i * 10,
+                lives_y,
+                8,
+                8,
+                BLIT_1BPP,
+            );
+        }
+
+        // Objective progress
+        let goal = self.objective_str();
+        if !goal.is_empty() {
+            self.colors_set(1);
+            rect(0, 151, goal.len() as u32 * 8 + 3, 9);
+            self.colors_set(if self.objective_met() { 4 } else { 3 });
+            text(&goal, 2, 152);
+        }
+
+        // Overheat banner
+        if self.drill_overheat {
+            self.colors_set(1);
+            rect(40, 24, 80, 12);
+            self.color_flash(4, 2, 16);
+            text("OVERHEAT!", 44, 26);
+        }
+
+        // Powerups UI, active ones stack down the side with their timers
+        let mut pu_y = 12;
+        for pu in POWERUP_TYPES {
+            let frames = self.powerup_frames[pu as usize];
+            if frames <= 1 {
+                continue;
+            }
+            self.colors_set(3);
+            // Blink when about to run out
+            if frames < 120 && frames % 20 < 10 {
                 self.colors_set(2);
-            } else {
-                self.colors_set(3);
             }
-            let pu_text = match self.powerup_cur {
-                PowerUp::SuperDrill => "SDRILL",
-                PowerUp::Invincible => "INVINC",
-                PowerUp::Magnet => "MAGNET",
-                PowerUp::None => "",
-            };
-            text(pu_text, 4, 12);
+            text(pu.name(), 4, pu_y);
+            rect(4, pu_y + 8, (frames as u32 / 20).min(48), 1);
+            pu_y += 11;
+        }
+
+        // Render umbrella
+        if self.powerup_on(PowerUp::Umbrella) {
+            let (x, y) = (self.player_pos.x as i32, self.player_pos.y as i32);
+            self.colors_set(3);
+            hline(x - 2, y - 4, PLAYER_SIZE as u32 + 4);
+            hline(x, y - 5, PLAYER_SIZE as u32);
+            vline(x + 4, y - 4, 4);
         }
 
         // Render invincibility overlay
-        if self.powerup_cur == PowerUp::Invincible && self.powerup_frames > 0 {
+        if self.powerup_on(PowerUp::Invincible) {
             self.colors_set(4);
             oval(
                 self.player_pos.x as i32 - 4,
@@ -3050,10 +7074,11 @@ impl GameMaster {
             // if (self.frame / 16) % self.gold_locs.len() as u32 == i as u32 {
             //     self.colors_set(4);
             // }
-            if self.player_pos.distance(&self.gold_locs[i]) < 48. {
+            let gold = self.gold_locs[i];
+            // X-Ray lights up far off gold like it was close by
+            if self.player_pos.distance(&gold) < 48. || self.powerup_on(PowerUp::XRay) {
                 self.color_flash(3, 4, 20);
             }
-            let gold = &self.gold_locs[i];
             blit(&gold_sprite, gold.x as i32, gold.y as i32, 8, 4, BLIT_1BPP);
         }
 
@@ -3073,6 +7098,35 @@ impl GameMaster {
             8,
             BLIT_1BPP,
         );
+        if self.door_locked() && self.player_pos.distance(&self.door_loc) < 24. {
+            self.colors_set(3);
+            // Show what's still needed, the boss trumps the objective
+            let need = if self.boss.is_some() {
+                "LOCKED".to_string()
+            } else {
+                self.objective_str()
+            };
+            let w = need.len() as i32 * 8;
+            let x = (self.door_loc.x as i32 + 4 - w / 2).clamp(0, 160 - w);
+            let y = if self.door_loc.y < 32 {
+                self.door_loc.y as i32 + 10
+            } else {
+                self.door_loc.y as i32 - 10
+            };
+            text(need, x, y);
+        }
+
+        // Render canary, flashes when hurt
+        if let Some(canary) = &self.canary {
+            let pos = canary.pos();
+            let hurt = canary.hit_frames > 0;
+            let sprite = self.sprite_frame(8, vec![CANARY1, CANARY2]);
+            self.colors_set(4);
+            if hurt {
+                self.color_flash(4, 2, 8);
+            }
+            blit(&sprite, pos.x as i32, pos.y as i32, 8, 8, BLIT_1BPP);
+        }
 
         // Render powerups
         if !self.powerup_taken {
@@ -3100,8 +7154,9 @@ impl GameMaster {
         // Render drones
         let drone_frame = (self.frame / 10) % 2;
         let drone_sprite = if drone_frame == 0 { &DRONE1 } else { &DRONE2 };
-        self.colors_set(4);
-        for drone in &self.drone_locs {
+        for i in 0..self.drone_locs.len() {
+            let drone = self.drone_locs[i];
+            self.enemy_color(Enemy::Drone, i);
             // rect(drone.x as i32, drone.y as i32, 6, 4);
             blit(
                 drone_sprite,
@@ -3117,7 +7172,7 @@ impl GameMaster {
         let fly_sprite = self.sprite_frame(6, vec![FLY1, FLY2]);
         for i in 0..self.fly_locs.len() {
             let fly = &self.fly_locs[i].clone();
-            self.colors_set(4);
+            self.enemy_color(Enemy::Fly, i);
             blit(&fly_sprite, fly.x as i32, fly.y as i32, 8, 8, BLIT_1BPP);
             if fly.y < 16 {
                 self.color_flash(4, 2, 20);
@@ -3127,8 +7182,9 @@ impl GameMaster {
 
         // Render sliders
         let slider_sprite = self.sprite_frame(6, vec![SLIDER1, SLIDER2]);
-        self.colors_set(4);
-        for slider in &self.slider_locs {
+        for i in 0..self.slider_locs.len() {
+            let slider = self.slider_locs[i];
+            self.enemy_color(Enemy::Slider, i);
             // rect(slider.x as i32, slider.y as i32, 6, 4);
             blit(
                 &slider_sprite,
@@ -3142,8 +7198,14 @@ impl GameMaster {
 
         // Render seekers
         let seeker_sprite = self.sprite_frame(6, vec![SEEKER1, SEEKER2]);
-        self.colors_set(4);
-        for seeker in &self.seeker_locs {
+        for i in 0..self.seeker_locs.len() {
+            let seeker = self.seeker_locs[i];
+            // Flag seekers that just spotted the player
+            if self.seeker_alert[i] > SEEKER_MEMORY - 30 {
+                self.color_flash(4, 2, 10);
+                blit(&EXC, seeker.x as i32, seeker.y as i32 - 9, 8, 8, BLIT_1BPP);
+            }
+            self.enemy_color(Enemy::Seeker, i);
             // rect(seeker.x as i32, seeker.y as i32, 6, 4);
             blit(
                 &seeker_sprite,
@@ -3159,7 +7221,7 @@ impl GameMaster {
         let bomber_sprite = self.sprite_frame(6, vec![BOMBER1, BOMBER2]);
         let bomber_locs: Vec<_> = self.bomber_locs.iter().cloned().collect();
         for (i, bomber) in bomber_locs.iter().enumerate() {
-            self.colors_set(4);
+            self.enemy_color(Enemy::Bomber, i);
             if self.bomber_times[i] > 0 {
                 self.color_flash(4, 3, 32 + self.bomber_times[i] as u32 * 8);
             }
@@ -3173,8 +7235,140 @@ impl GameMaster {
             );
         }
 
-        // Render player
+        // Render ufos
+        let ufo_sprite = self.sprite_frame(6, vec![UFO1, UFO2]);
+        for i in 0..self.ufo_locs.len() {
+            let ufo = self.ufo_locs[i];
+            let time = self.ufo_times[i];
+            let top = ufo.y + 8;
+            if time < UFO_BEAM_FRAMES {
+                let end = self.ufo_beam_end(&ufo);
+                for bx in 0..4 {
+                    self.colors_set(3 + ((bx + self.frame / 4) % 2) as u16);
+                    vline(
+                        (ufo.x + 2) as i32 + bx as i32,
+                        top as i32,
+                        (end - top) as u32,
+                    );
+                }
+            } else if time < UFO_BEAM_FRAMES + 40 {
+                // Warn before the beam turns on
+                self.color_flash(2, 3, 8);
+                vline((ufo.x + 4) as i32, top as i32, 8);
+            }
+            self.enemy_color(Enemy::Ufo, i);
+            blit(&ufo_sprite, ufo.x as i32, ufo.y as i32, 8, 8, BLIT_1BPP);
+        }
+
+        // Render turrets
+        for i in 0..self.turret_locs.len() {
+            let turret = self.turret_locs[i];
+            // Eye opens up right before firing
+            let sprite = if self.turret_times[i] < 20 {
+                TURRET2
+            } else {
+                TURRET1
+            };
+            self.enemy_color(Enemy::Turret, i);
+            blit(&sprite, turret.x as i32, turret.y as i32, 8, 8, BLIT_1BPP);
+        }
+
+        // Render worms
+        let head_sprite = self.sprite_frame(6, vec![WORM_HEAD1, WORM_HEAD2]);
+        for i in 0..self.worm_locs.len() {
+            self.enemy_color(Enemy::Worm, i);
+            let segs: Vec<Pos> = self.worm_bodies[i].segments().copied().collect();
+            for seg in segs.iter().rev() {
+                blit(&WORM_BODY, seg.x as i32, seg.y as i32, 8, 8, BLIT_1BPP);
+            }
+            let head = self.worm_locs[i];
+            blit(&head_sprite, head.x as i32, head.y as i32, 8, 8, BLIT_1BPP);
+        }
+
+        // Render boss
+        if let Some(boss) = self.boss {
+            let sprite = if (self.frame / 8) % 2 == 0 {
+                BOSS1
+            } else {
+                BOSS2
+            };
+            self.colors_set(4);
+            if boss.hit_frames > 0 {
+                self.color_flash(4, 2, 4);
+            }
+            blit(
+                &sprite,
+                boss.pos.x as i32,
+                boss.pos.y as i32,
+                16,
+                16,
+                BLIT_1BPP,
+            );
+        }
+
+        // Render projectiles
+        for i in 0..self.projectiles.len() {
+            let p = self.projectiles[i];
+            if p.life == 0 {
+                continue;
+            }
+            let pos = p.pos();
+            self.color_flash(4, 3, 6);
+            rect(pos.x as i32, pos.y as i32, 2, 2);
+        }
+
+        // Render death effects
+        for i in 0..self.fx_locs.len() {
+            let fx = self.fx_locs[i];
+            let size = (FX_FRAMES - self.fx_times[i]) as i32 + 2;
+            self.color_flash(0x40, 0x30, 4);
+            oval(
+                fx.x as i32 + 4 - size / 2,
+                fx.y as i32 + 2 - size / 2,
+                size as u32,
+                size as u32,
+            );
+        }
+
+        // Render coolant
+        for i in 0..self.coolant_locs.len() {
+            let c = self.coolant_locs[i];
+            self.color_flash(4, 3, 40);
+            blit(&COOLANT, c.x as i32, c.y as i32, 8, 8, BLIT_1BPP);
+        }
+
+        // Render bombs
+        let bomb_sprite = self.sprite_frame(12, vec![BOMB1, BOMB2]);
+        for i in 0..self.bomb_locs.len() {
+            let bomb = self.bomb_locs[i];
+            self.color_flash(4, 3, 8 + self.bomb_times[i] as u32 / 2);
+            blit(&bomb_sprite, bomb.x as i32, bomb.y as i32, 8, 8, BLIT_1BPP);
+        }
+
+        // Respawn beam closing in on the player
+        if self.respawn_frames > 0 {
+            let w = self.respawn_frames as i32 / 6;
+            let x = self.player_pos.x as i32 + 4;
+            self.color_flash(3, 4, 8);
+            rect(x - w, 0, (w * 2 + 1) as u32, self.player_pos.y as u32 + 8);
+            // Continues are already paid for, lives drop gold
+            if self.respawn_gold > 0 {
+                let lost = format!("-{}", self.respawn_gold);
+                self.colors_set(1);
+                rect(36, 60, 88, 22);
+                self.colors_set(4);
+                text("LIFE LOST", 44, 62);
+                self.colors_set(3);
+                text(&lost, 44, 72);
+                blit(&GOLDLRG1, 46 + lost.len() as i32 * 8, 72, 8, 8, BLIT_1BPP);
+            }
+        }
+
+        // Render player, blinks while it can't be hurt
         self.colors_set(4);
+        if self.inv_frames > 0 && (self.frame / 4) % 2 == 0 {
+            self.colors_set(3);
+        }
         let player_flags = match self.dir {
             0 => self.player_flags_last,
             1 => BLIT_1BPP | BLIT_FLIP_X,
@@ -3182,9 +7376,10 @@ impl GameMaster {
             _ => self.player_flags_last,
         };
         self.player_flags_last = player_flags;
-        let mut player_sprite = self.sprite_frame(12, vec![SMILEY1, SMILEY2, SMILEY3]);
+        let sprites = self.miner().sprites;
+        let mut player_sprite = self.sprite_frame(12, sprites.to_vec());
         if self.dir == 0 {
-            player_sprite = SMILEY1;
+            player_sprite = sprites[0];
         }
         if self.hp == 0 {
             player_sprite = SMILEYDEAD;
@@ -3229,7 +7424,11 @@ impl GameMaster {
         };
         if drill_show && self.is_drilling {
             // let mut drill_sprite = if drill_frame == 0 { &DRILL1 } else { &DRILL2 };
-            let drill_sprite_n = self.sprite_frame(12, vec![DRILL1, DRILL2]);
+            let drill_sprite_n = if self.drill_owns(DrillPart::Wide) {
+                self.sprite_frame(12, vec![DRILLW1, DRILLW2])
+            } else {
+                self.sprite_frame(12, vec![DRILL1, DRILL2])
+            };
             let drill_sprite_d = self.sprite_frame(12, vec![DRILLD1, DRILLD2]);
             let drill_sprite = match self.dir {
                 0..4 => drill_sprite_n,
@@ -3239,6 +7438,15 @@ impl GameMaster {
                 8 => drill_sprite_d,
                 _ => drill_sprite_n,
             };
+            // Longer shafts push the bit out, piercing bits glint
+            let reach = self.drill_level(DrillPart::Reach) as i16 * 3;
+            let drill_off = Pos::new(
+                drill_off.x + drill_off.x.signum() * reach,
+                drill_off.y + drill_off.y.signum() * reach,
+            );
+            if self.drill_owns(DrillPart::Pierce) {
+                self.color_flash(4, 3, 8);
+            }
             blit(
                 &drill_sprite,
                 (self.player_pos.x + drill_off.x) as i32,
@@ -3280,6 +7488,7 @@ impl GameMaster {
     fn start(&mut self) {
         self.palette_set(PALS[self.pal_index as usize]);
         self.world = MiniBitVec::new();
+        self.save = Save::load();
     }
 
     // TODO: Frame inc can happen everywhere?
@@ -3288,9 +7497,18 @@ impl GameMaster {
         self.up_sc_start();
         self.up_sc_main();
         self.up_sc_gameover();
+        self.up_sc_continue();
         self.up_sc_shop();
+        self.up_sc_workshop();
+        self.up_sc_draft();
+        self.up_sc_miners();
+        self.up_sc_mutators();
+        self.up_sc_unlocks();
+        self.up_sc_gallery();
         self.up_sc_transition();
         self.up_music();
+        self.up_toast();
+        self.gamepad_last = unsafe { *GAMEPAD1 };
         self.frame += 1;
         // No input frames countdown
         self.no_input_frames = self.no_input_frames.saturating_sub(1);
@@ -3310,11 +7528,20 @@ impl GameMaster {
         // DRAW
         self.render_sc_intro();
         self.render_sc_main();
+        self.render_shake();
         // NOTE: Other screens only render if active
         self.render_sc_start();
         self.render_sc_gameover();
         self.render_sc_shop();
+        self.render_sc_workshop();
+        self.render_sc_draft();
+        self.render_sc_miners();
+        self.render_sc_continue();
+        self.render_sc_mutators();
+        self.render_sc_unlocks();
+        self.render_sc_gallery();
         self.render_sc_transition();
+        self.render_toast();
         // No input overlay
         self.render_no_input();
         // Debug